@@ -0,0 +1,64 @@
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use rust_c_riscv::{ContinuedFraction, gcd, gcd_binary};
+
+// Consecutive Fibonacci numbers are the worst case for Euclid, which the C `gcd` and
+// `from_rational` both run, LARGE is the largest such pair that fits in an i32
+const SMALL: (i32, i32) = (89, 55);
+const LARGE: (i32, i32) = (1_836_311_903, 1_134_903_170);
+
+fn bench_gcd(c: &mut Criterion) {
+    let mut group = c.benchmark_group("gcd");
+
+    for (name, (p, q)) in [("small", SMALL), ("large", LARGE)] {
+        group.bench_function(format!("c/{}", name), |b| {
+            b.iter(|| gcd(black_box(p), black_box(q)))
+        });
+        group.bench_function(format!("binary/{}", name), |b| {
+            b.iter(|| gcd_binary(black_box(p), black_box(q)))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_from_rational(c: &mut Criterion) {
+    let mut group = c.benchmark_group("from_rational");
+
+    for (name, (p, q)) in [("small", SMALL), ("large", LARGE)] {
+        group.bench_function(name, |b| {
+            b.iter(|| ContinuedFraction::from_rational(black_box(p), black_box(q)))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_convergents(c: &mut Criterion) {
+    let cf = ContinuedFraction::from_rational(LARGE.0, LARGE.1);
+
+    c.bench_function("convergents", |b| b.iter(|| black_box(&cf).convergents()));
+}
+
+fn bench_best_approximation(c: &mut Criterion) {
+    let pi =
+        ContinuedFraction::from_coefficients(&[3, 7, 15, 1, 292, 1, 1, 1, 2, 1, 3, 1]).unwrap();
+    let phi = ContinuedFraction::from_rational(LARGE.0, LARGE.1);
+
+    c.bench_function("best_approximation/pi", |b| {
+        b.iter(|| black_box(&pi).best_approximation(black_box(1000)))
+    });
+    c.bench_function("best_approximation/phi", |b| {
+        b.iter(|| black_box(&phi).best_approximation(black_box(100_000)))
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_gcd,
+    bench_from_rational,
+    bench_convergents,
+    bench_best_approximation
+);
+criterion_main!(benches);
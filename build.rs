@@ -1,19 +1,71 @@
 use std::env;
 use std::error::Error;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Collect the `.c` and `.S` files under `dir`, recursively.
+fn sources(dir: &Path, found: &mut Vec<PathBuf>) -> Result<(), Box<dyn Error>> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            sources(&path, found)?;
+        } else if matches!(path.extension().and_then(|e| e.to_str()), Some("c" | "S")) {
+            found.push(path);
+        }
+    }
+
+    Ok(())
+}
 
 fn main() -> Result<(), Box<dyn Error>> {
-    cc::Build::new().file("some-c/gcd.c").compile("gcd");
+    let out_path = PathBuf::from(env::var("OUT_DIR")?);
+    let arch = env::var("CARGO_CFG_TARGET_ARCH")?;
+    let asm_gcd = env::var_os("CARGO_FEATURE_ASM_GCD").is_some();
+
+    let mut files = Vec::new();
+    sources(Path::new("csrc"), &mut files)?;
+    files.sort();
+
+    // Link the C code as a plain static archive so its symbols can be overridden
+    let mut build = cc::Build::new();
+    build.cargo_metadata(false);
+
+    // A weak `gcd` gives way to any other definition, eg. from a hardware math library
+    if env::var_os("CARGO_FEATURE_GCD_OVERRIDE").is_some() {
+        build.define("GCD_WEAK", None);
+    }
+
+    for file in files {
+        if file.extension().is_some_and(|e| e == "S") {
+            // Assembly is named `<name>_<arch>.S` and only replaces the C code with `asm-gcd`
+            let stem = file
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default();
+            if !asm_gcd || !stem.ends_with(&format!("_{}", arch)) {
+                continue;
+            }
+
+            build.define("GCD_ASM", None);
+        }
+
+        build.file(file);
+    }
+
+    build.compile("gcd_impl");
+
+    println!("cargo:rustc-link-search=native={}", out_path.display());
+    println!("cargo:rustc-link-lib=static=gcd_impl");
 
     let bindings = bindgen::builder()
-        .header("some-c/gcd.h")
+        .header("csrc/math.h")
         .use_core()
         .generate()?;
 
-    let out_path = PathBuf::from(env::var("OUT_DIR")?);
     bindings.write_to_file(out_path.join("bindings.rs"))?;
 
-    println!("cargo:rerun-if-changed=some-c");
+    println!("cargo:rerun-if-changed=csrc");
     println!("cargo:rerun-if-changed=build.rs");
 
     Ok(())
@@ -0,0 +1,2 @@
+// Single entry point for bindgen, add new headers under math/ here
+#include "math/gcd.h"
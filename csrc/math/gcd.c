@@ -0,0 +1,96 @@
+#include "gcd.h"
+
+// Replaced by gcd_riscv64.S when built with the `asm-gcd` feature
+#ifndef GCD_ASM
+// With the `gcd-override` feature any strong `gcd` linked into the program wins
+#ifdef GCD_WEAK
+__attribute__((weak))
+#endif
+int gcd(Pair* ps) {
+    // Euclid on the magnitudes, unsigned so that negating INT_MIN is defined
+    unsigned a = ps->n < 0 ? -(unsigned)ps->n : (unsigned)ps->n;
+    unsigned b = ps->m < 0 ? -(unsigned)ps->m : (unsigned)ps->m;
+
+    while (b != 0) {
+        unsigned r = a % b;
+        a = b;
+        b = r;
+    }
+
+    return (int)a;
+}
+#endif
+
+long long lcm(Pair* ps) {
+    if (ps->n == 0 || ps->m == 0)
+        return 0;
+
+    // Unsigned magnitudes, negating INT_MIN would overflow
+    unsigned a = ps->n < 0 ? -(unsigned)ps->n : (unsigned)ps->n;
+    unsigned b = ps->m < 0 ? -(unsigned)ps->m : (unsigned)ps->m;
+
+    // gcd takes the magnitudes itself, a result of 2^31 comes back as INT_MIN
+    unsigned g = (unsigned)gcd(ps);
+
+    // Divide first and widen so the product cannot overflow
+    return (long long)(a / g) * b;
+}
+
+int extended_gcd(ExtendedPair* ep) {
+    int old_r = ep->a, r = ep->b;
+    int old_s = 1, s = 0;
+    int old_t = 0, t = 1;
+
+    while (r != 0) {
+        int q = old_r / r;
+        int tmp;
+
+        tmp = r;
+        r = old_r - q * r;
+        old_r = tmp;
+
+        tmp = s;
+        s = old_s - q * s;
+        old_s = tmp;
+
+        tmp = t;
+        t = old_t - q * t;
+        old_t = tmp;
+    }
+
+    // Keep the gcd non-negative, flipping the coefficients along with it
+    if (old_r < 0) {
+        old_r = -old_r;
+        old_s = -old_s;
+        old_t = -old_t;
+    }
+
+    ep->s = old_s;
+    ep->t = old_t;
+
+    return old_r;
+}
+
+uint32_t gcd_u32(uint32_t a, uint32_t b) {
+    while (b != 0) {
+        uint32_t r = a % b;
+        a = b;
+        b = r;
+    }
+
+    return a;
+}
+
+int64_t gcd64(Pair64* ps) {
+    // Unsigned magnitudes, negating INT64_MIN would overflow
+    uint64_t a = ps->n < 0 ? -(uint64_t)ps->n : (uint64_t)ps->n;
+    uint64_t b = ps->m < 0 ? -(uint64_t)ps->m : (uint64_t)ps->m;
+
+    while (b != 0) {
+        uint64_t r = a % b;
+        a = b;
+        b = r;
+    }
+
+    return (int64_t)a;
+}
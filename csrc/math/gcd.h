@@ -0,0 +1,28 @@
+#include <stdint.h>
+
+typedef struct Pair {
+    int n;
+    int m;
+} Pair;
+
+int gcd(Pair* ps);
+
+long long lcm(Pair* ps);
+
+typedef struct ExtendedPair {
+    int a;
+    int b;
+    int s;
+    int t;
+} ExtendedPair;
+
+int extended_gcd(ExtendedPair* ep);
+
+uint32_t gcd_u32(uint32_t a, uint32_t b);
+
+typedef struct Pair64 {
+    int64_t n;
+    int64_t m;
+} Pair64;
+
+int64_t gcd64(Pair64* ps);
@@ -1,6 +0,0 @@
-typedef struct Pair {
-    int n;
-    int m;
-} Pair;
-
-int gcd(Pair* ps);
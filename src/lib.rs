@@ -1,36 +1,643 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::error::Error;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Add, Mul, Neg};
+use core::str::FromStr;
+
 mod bindings {
     include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 }
 
+/// Greatest common divisor of `|n|` and `|m|`, `gcd(0, x) == |x|` and `gcd(0, 0) == 0`.
+///
+/// Calls the C symbol `int gcd(Pair*)` from the static library `gcd_impl`. With the
+/// `gcd-override` feature another definition of that symbol takes its place at link time.
+///
+/// `i32::MIN` is unsupported since `|i32::MIN|` does not fit in an `i32`, debug builds
+/// assert against it; see [`gcd_checked`].
 pub fn gcd(n: i32, m: i32) -> i32 {
+    SafePair::new(n, m).gcd()
+}
+
+/// The C `Pair` struct behind a safe interface, for calling the C routines without
+/// writing `unsafe`.
+#[derive(Debug, Clone, Copy)]
+pub struct SafePair(bindings::Pair);
+
+impl SafePair {
+    /// Pair of `n` and `m`.
+    pub fn new(n: i32, m: i32) -> Self {
+        Self(bindings::Pair { n, m })
+    }
+
+    /// First element.
+    pub fn n(&self) -> i32 {
+        self.0.n
+    }
+
+    /// Second element.
+    pub fn m(&self) -> i32 {
+        self.0.m
+    }
+
+    /// Calls the C `int gcd(Pair*)` on this pair, same contract as [`gcd`].
+    pub fn gcd(&mut self) -> i32 {
+        let (n, m) = (self.0.n, self.0.m);
+        debug_assert!(
+            n != i32::MIN && m != i32::MIN,
+            "gcd does not support i32::MIN"
+        );
+
+        // Answer zero arguments here, whichever `gcd` symbol ends up linked
+        if n == 0 {
+            return m.abs();
+        }
+        if m == 0 {
+            return n.abs();
+        }
+
+        unsafe { bindings::gcd(&mut self.0) }
+    }
+}
+
+/// Error returned by [`gcd_checked`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GcdError {
+    /// An input is `i32::MIN`, whose absolute value does not fit in an `i32`.
+    Overflow,
+}
+
+impl fmt::Display for GcdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overflow => write!(f, "gcd input overflows i32"),
+        }
+    }
+}
+
+impl Error for GcdError {}
+
+/// Non-negative `gcd(|a|, |b|)`, or [`GcdError::Overflow`] if either input is `i32::MIN`.
+pub fn gcd_checked(a: i32, b: i32) -> Result<i32, GcdError> {
+    match (a.checked_abs(), b.checked_abs()) {
+        (Some(a), Some(b)) => Ok(gcd(a, b)),
+        _ => Err(GcdError::Overflow),
+    }
+}
+
+/// Greatest common divisor of two unsigned integers, `gcd_u32(0, x) == x`.
+pub fn gcd_u32(a: u32, b: u32) -> u32 {
+    unsafe { bindings::gcd_u32(a, b) }
+}
+
+/// Non-negative greatest common divisor of two 64-bit integers, `gcd64(0, x) == |x|`.
+///
+/// Calls the C symbol `int64_t gcd64(Pair64*)`, which works on unsigned magnitudes so
+/// `i64::MIN` is fine as long as the result fits. Panics when it is `2^63`, i.e. for
+/// `i64::MIN` paired with 0 or itself.
+pub fn gcd64(n: i64, m: i64) -> i64 {
+    let pair = &mut bindings::Pair64 { n, m };
+    let d = unsafe { bindings::gcd64(pair) };
+
+    // 2^63 comes back wrapped round to i64::MIN
+    assert!(d >= 0, "gcd64({}, {}) = 2^63 does not fit in i64", n, m);
+    d
+}
+
+/// All-Rust binary gcd (Stein's algorithm) of `|a|` and `|b|`, using only shifts and
+/// subtraction. Panics when the result `2^31` does not fit, i.e. for `i32::MIN` paired
+/// with itself or zero.
+pub fn gcd_binary(a: i32, b: i32) -> i32 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+
+    if a == 0 || b == 0 {
+        return i32::try_from(a | b).expect("gcd overflowed i32");
+    }
+
+    // Factor out the powers of two common to both
+    let shift = (a | b).trailing_zeros();
+    a >>= a.trailing_zeros();
+
+    loop {
+        b >>= b.trailing_zeros();
+        if a > b {
+            (a, b) = (b, a);
+        }
+
+        b -= a;
+        if b == 0 {
+            break;
+        }
+    }
+
+    i32::try_from(a << shift).expect("gcd overflowed i32")
+}
+
+/// Order two Egyptian fraction expansions by fewer terms, then by smaller largest
+/// denominator; `Ordering::Less` means `a` is the better one.
+pub fn cmp_egyptian_fractions(a: &[i32], b: &[i32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().max().cmp(&b.iter().max()))
+}
+
+/// Greatest common divisor of all `values`, taken as absolute values.
+///
+/// An empty slice gives 0, the identity of `gcd`. `i32::MIN` is unsupported.
+pub fn gcd_slice(values: &[i32]) -> i32 {
+    let mut d = 0;
+
+    for &value in values {
+        d = gcd(d, value.abs());
+
+        // Nothing can reduce a gcd of 1 further
+        if d == 1 {
+            break;
+        }
+    }
+
+    d
+}
+
+/// Whether `a` and `b` share no common factor, `coprime(0, 1)` holds but `coprime(0, 0)` does not.
+pub fn coprime(a: i32, b: i32) -> bool {
+    // unsigned_abs keeps i32::MIN in range
+    gcd_u32(a.unsigned_abs(), b.unsigned_abs()) == 1
+}
+
+/// Least common multiple of `n` and `m`, panics if the result does not fit in an `i32`.
+pub fn lcm(n: i32, m: i32) -> i32 {
+    lcm_checked(n, m).expect("lcm overflowed i32")
+}
+
+/// Least common multiple of `n` and `m`, or `None` if the result does not fit in an `i32`.
+pub fn lcm_checked(n: i32, m: i32) -> Option<i32> {
+    if n == 0 || m == 0 {
+        return Some(0);
+    }
+
+    // |i32::MIN| cannot be represented, and any non-zero multiple of it overflows anyway
+    if n == i32::MIN || m == i32::MIN {
+        return None;
+    }
+
     let pair = &mut bindings::Pair { n, m };
-    unsafe { bindings::gcd(pair) }
+    i32::try_from(unsafe { bindings::lcm(pair) }).ok()
+}
+
+/// Extended Euclidean algorithm, returns `(g, s, t)` such that `a*s + b*t == g` with `g = gcd(a, b) >= 0`.
+pub fn extended_gcd(a: i32, b: i32) -> (i32, i32, i32) {
+    // The C division `i32::MIN / -1` would overflow
+    assert!(
+        a != i32::MIN && b != i32::MIN,
+        "extended_gcd does not support i32::MIN"
+    );
+
+    let pair = &mut bindings::ExtendedPair { a, b, s: 0, t: 0 };
+    let g = unsafe { bindings::extended_gcd(pair) };
+    let (s, t) = (pair.s, pair.t);
+
+    debug_assert_eq!(
+        a as i64 * s as i64 + b as i64 * t as i64,
+        g as i64,
+        "Bézout identity failed for extended_gcd({}, {})",
+        a,
+        b
+    );
+
+    (g, s, t)
+}
+
+/// Inverse of `a` modulo `m` in `[0, m)`, or `None` if `gcd(a, m) != 1` or `m <= 0`.
+pub fn mod_inverse(a: i32, m: i32) -> Option<i32> {
+    if m <= 0 {
+        return None;
+    }
+
+    let (g, s, _) = extended_gcd(a.rem_euclid(m), m);
+
+    (g == 1).then(|| s.rem_euclid(m))
+}
+
+/// Chinese remainder theorem: the unique `x` in `[0, lcm(m1, m2))` with `x ≡ r1 (mod m1)`
+/// and `x ≡ r2 (mod m2)`.
+///
+/// `None` if the congruences are incompatible, i.e. `gcd(m1, m2)` does not divide
+/// `r2 - r1`, if a modulus is not positive, or if `x` does not fit in an `i32`.
+pub fn crt(r1: i32, m1: i32, r2: i32, m2: i32) -> Option<i32> {
+    if m1 <= 0 || m2 <= 0 {
+        return None;
+    }
+
+    // m1*s + m2*t == g
+    let (g, s, _) = extended_gcd(m1, m2);
+    let (r1, m1, r2, m2, g, s) = (
+        r1 as i128, m1 as i128, r2 as i128, m2 as i128, g as i128, s as i128,
+    );
+
+    let diff = r2 - r1;
+    if diff % g != 0 {
+        return None;
+    }
+
+    // x = r1 + m1*k with m1*k ≡ r2 - r1 (mod m2), so k ≡ (diff/g)*s (mod m2/g)
+    let k = (diff / g * s).rem_euclid(m2 / g);
+    let x = (r1 + m1 * k).rem_euclid(m1 / g * m2);
+
+    i32::try_from(x).ok()
+}
+
+/// Mediant `(p + r)/(q + s)` of the fractions `p/q` and `r/s`.
+pub fn mediant(a: (i32, i32), b: (i32, i32)) -> (i32, i32) {
+    (a.0 + b.0, a.1 + b.1)
+}
+
+/// Like [`mediant`], but returns `None` if either sum overflows `i32`.
+pub fn mediant_checked(a: (i32, i32), b: (i32, i32)) -> Option<(i32, i32)> {
+    Some((a.0.checked_add(b.0)?, a.1.checked_add(b.1)?))
+}
+
+/// Farey sequence `F_n`, the reduced fractions in `[0, 1]` with denominator at most `n`,
+/// in increasing order. Empty for `n == 0`, panics if `n` does not fit in an `i32`.
+pub fn farey_sequence(n: u32) -> Vec<(i32, i32)> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let n = i32::try_from(n).expect("farey order overflowed i32");
+
+    // Each term follows from the previous two: for neighbours a/b < c/d the next one is
+    // (k*c - a)/(k*d - b) with k = floor((n + b) / d)
+    let (mut a, mut b, mut c, mut d) = (0, 1, 1, n);
+    let mut sequence = vec![(a, b)];
+
+    while c <= n {
+        let k = (n + b) / d;
+        (a, b, c, d) = (c, d, k * c - a, k * d - b);
+        sequence.push((a, b));
+    }
+
+    sequence
+}
+
+/// C-compatible pair `n/m` with the same layout as the `Pair` struct in `gcd.h`, for
+/// passing rationals across a C boundary.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RatPair {
+    pub n: i32,
+    pub m: i32,
+}
+
+// Keep in sync with the bindgen-generated `Pair`
+const _: () = assert!(
+    size_of::<RatPair>() == size_of::<bindings::Pair>()
+        && align_of::<RatPair>() == align_of::<bindings::Pair>()
+);
+
+/// Step from a node of the Stern-Brocot tree to one of its children.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Towards the smaller child.
+    Left,
+    /// Towards the larger child.
+    Right,
+}
+
+/// Side of the value on which a convergent lies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parity {
+    /// Even index, at or below the value.
+    Even,
+    /// Odd index, at or above the value.
+    Odd,
+}
+
+/// 2x2 integer matrix of the convergent recurrence, `[[h_n, h_{n-1}], [k_n, k_{n-1}]]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConvMatrix(pub [[i32; 2]; 2]);
+
+impl ConvMatrix {
+    /// Identity matrix, the product over no coefficients.
+    pub const IDENTITY: Self = Self([[1, 0], [0, 1]]);
+
+    /// Matrix `[[a, 1], [1, 0]]` contributed by a single coefficient `a`.
+    pub fn coefficient(a: i32) -> Self {
+        Self([[a, 1], [1, 0]])
+    }
+
+    /// Raise to the `n`-th power by repeated squaring, e.g. to repeat a period `n` times.
+    pub fn pow(self, mut n: u32) -> Self {
+        let (mut base, mut result) = (self, Self::IDENTITY);
+
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            n >>= 1;
+        }
+
+        result
+    }
+
+    /// Convergent `(h_n, k_n)` in the first column.
+    pub fn convergent(&self) -> (i32, i32) {
+        (self.0[0][0], self.0[1][0])
+    }
+}
+
+impl Mul for ConvMatrix {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let ([[a, b], [c, d]], [[e, f], [g, h]]) = (self.0, other.0);
+
+        Self([
+            [a * e + b * g, a * f + b * h],
+            [c * e + d * g, c * f + d * h],
+        ])
+    }
 }
 
 /// Continued fraction representation of a rational number.
 /// A rational p/q is represented as [a0; a1, a2, ...] where:
 /// p/q = a0 + 1/(a1 + 1/(a2 + 1/(...)))
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct ContinuedFraction {
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "deserialize_coefficients")
+    )]
     coefficients: Vec<i32>,
 }
 
+/// Deserialize the coefficient array, validating it like [`ContinuedFraction::from_coefficients`].
+#[cfg(feature = "serde")]
+fn deserialize_coefficients<'de, D>(deserializer: D) -> Result<Vec<i32>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let coefficients = <Vec<i32> as serde::Deserialize>::deserialize(deserializer)?;
+
+    ContinuedFraction::try_from(coefficients)
+        .map(|cf| cf.coefficients)
+        .map_err(serde::de::Error::custom)
+}
+
 impl ContinuedFraction {
+    /// The zero fraction `[0]`.
+    pub fn zero() -> Self {
+        Self {
+            coefficients: vec![0],
+        }
+    }
+
+    /// The unit fraction `[1]`.
+    pub fn one() -> Self {
+        Self {
+            coefficients: vec![1],
+        }
+    }
+
     /// Convert a rational number p/q to its continued fraction representation.
-    pub fn from_rational(mut p: i32, mut q: i32) -> Self {
-        let d = gcd(p.abs(), q.abs());
-        p /= d;
-        q /= d;
+    ///
+    /// Panics if `q == 0` or if the reduced fraction does not fit in `i32`, see
+    /// [`try_from_rational`](Self::try_from_rational).
+    pub fn from_rational(p: i32, q: i32) -> Self {
+        assert!(
+            q != 0,
+            "denominator must be non-zero, got from_rational({}, 0)",
+            p
+        );
 
-        if q < 0 {
-            p = -p;
-            q = -q;
+        match Self::try_from_rational(p, q) {
+            Ok(cf) => cf,
+            Err(err) => panic!("{}", err),
         }
+    }
 
-        let mut coefficients = Vec::new();
+    /// Fallible [`from_rational`](Self::from_rational).
+    ///
+    /// `i32::MIN` is accepted as long as the reduced fraction fits in `i32` with a positive
+    /// denominator, e.g. `i32::MIN/2`, but not `i32::MIN/-1` or `1/i32::MIN`; those yield
+    /// [`ContinuedFractionError::InvalidInput`]. A zero denominator yields
+    /// [`ContinuedFractionError::DivisionByZero`].
+    pub fn try_from_rational(p: i32, q: i32) -> Result<Self, ContinuedFractionError> {
+        if q == 0 {
+            return Err(ContinuedFractionError::DivisionByZero);
+        }
+
+        // |i32::MIN| does not fit in an i32, promote to the 64-bit gcd for that case only
+        let d = match (p.checked_abs(), q.checked_abs()) {
+            (Some(p_abs), Some(q_abs)) => gcd(p_abs, q_abs) as i64,
+            _ => gcd64(p as i64, q as i64),
+        };
+
+        // Already coprime inputs skip the divisions
+        let (mut p_wide, mut q_wide) = match d {
+            1 => (p as i64, q as i64),
+            d => (p as i64 / d, q as i64 / d),
+        };
+
+        if q_wide < 0 {
+            p_wide = -p_wide;
+            q_wide = -q_wide;
+        }
+
+        let (Ok(mut p), Ok(mut q)) = (i32::try_from(p_wide), i32::try_from(q_wide)) else {
+            return Err(ContinuedFractionError::InvalidInput(format!(
+                "{}/{} overflows when normalizing the sign",
+                p, q
+            )));
+        };
 
+        // By Lamé's theorem Euclid takes fewer than 2*log2(min(|p|, q)) + 2 steps
+        let smaller = p.unsigned_abs().min(q as u32);
+        let capacity = 2 * smaller.checked_ilog2().unwrap_or(0) as usize + 2;
+        let mut coefficients = Vec::with_capacity(capacity);
+
+        // Floor division keeps every coefficient after a0 positive, even for negative p
         while q != 0 {
-            coefficients.push(p / q);
-            (p, q) = (q, p % q);
+            coefficients.push(p.div_euclid(q));
+            (p, q) = (q, p.rem_euclid(q));
+        }
+
+        Ok(Self { coefficients })
+    }
+
+    /// Continued fraction of `sqrt(n)` as `[a0; a1, ..., ar]` covering one full period,
+    /// together with the period length `r`.
+    ///
+    /// The period repeats forever, so truncate as needed. A perfect square gives `([a0], 0)`.
+    pub fn from_sqrt(n: u32) -> (Self, usize) {
+        let a0 = n.isqrt();
+        let mut coefficients = vec![a0 as i32];
+
+        if a0 * a0 == n {
+            return (Self { coefficients }, 0);
+        }
+
+        // m_{k+1} = d_k*a_k - m_k, d_{k+1} = (n - m_{k+1}^2) / d_k, a_{k+1} = (a0 + m_{k+1}) / d_{k+1}
+        let (n, a0) = (n as u64, a0 as u64);
+        let (mut m, mut d, mut a) = (0, 1, a0);
+
+        // The period ends with the first coefficient equal to 2*a0
+        while a != 2 * a0 {
+            m = d * a - m;
+            d = (n - m * m) / d;
+            a = (a0 + m) / d;
+            coefficients.push(a as i32);
+        }
+
+        let period = coefficients.len() - 1;
+        (Self { coefficients }, period)
+    }
+
+    /// Fundamental solution `(x, y)` of the Pell equation `x^2 - d*y^2 = 1`.
+    ///
+    /// Returns `None` if `d` is a perfect square, or if the solution does not fit in a `u64`.
+    pub fn pell_solution(d: u32) -> Option<(u64, u64)> {
+        let (cf, period) = Self::from_sqrt(d);
+        if period == 0 {
+            return None;
+        }
+
+        // The solution is the convergent closing the first period of even length
+        let terms = if period % 2 == 0 { period } else { 2 * period };
+        let coefficient = |i: usize| match i {
+            0 => cf.coefficients[0] as u64,
+            _ => cf.coefficients[1 + (i - 1) % period] as u64,
+        };
+
+        let (mut h_2, mut k_2) = (0u64, 1u64); // h_{n-2}, k_{n-2}
+        let (mut h_1, mut k_1) = (1u64, 0u64); // h_{n-1}, k_{n-1}
+
+        for i in 0..terms {
+            let a = coefficient(i);
+            let h = a.checked_mul(h_1)?.checked_add(h_2)?;
+            let k = a.checked_mul(k_1)?.checked_add(k_2)?;
+
+            (h_2, k_2) = (h_1, k_1);
+            (h_1, k_1) = (h, k);
+        }
+
+        Some((h_1, k_1))
+    }
+
+    /// First `terms` coefficients of `e = [2; 1, 2, 1, 1, 4, 1, 1, 6, ...]`, straight from
+    /// the pattern. Panics if `terms == 0`.
+    pub fn cf_e(terms: usize) -> Self {
+        assert!(terms > 0, "cf_e needs at least one term");
+
+        let coefficients = (0..terms)
+            .map(|i| match i {
+                0 => 2,
+                // Every third coefficient, starting at a2, is the next even number
+                _ if i % 3 == 2 => (2 * (i + 1) / 3) as i32,
+                _ => 1,
+            })
+            .collect();
+
+        Self { coefficients }
+    }
+
+    /// First `terms` coefficients of the golden ratio `phi = [1; 1, 1, ...]`, whose
+    /// convergents are ratios of consecutive Fibonacci numbers. Panics if `terms == 0`.
+    pub fn phi(terms: usize) -> Self {
+        assert!(terms > 0, "phi needs at least one term");
+
+        Self {
+            coefficients: vec![1; terms],
+        }
+    }
+
+    /// Pseudo-random fraction with `terms` coefficients for tests and benchmarks, `a0` in
+    /// `0..=100` and the rest in `1..=100`, panics if `terms == 0`. The same `seed` always
+    /// gives the same fraction.
+    pub fn generate_random(seed: u64, terms: usize) -> Self {
+        assert!(terms > 0, "a continued fraction needs at least one term");
+
+        // Knuth's MMIX LCG, the high bits have the longest period
+        let mut state = seed;
+        let mut next = |low: u64| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (low + (state >> 33) % (101 - low)) as i32
+        };
+
+        let coefficients = (0..terms).map(|i| next(u64::from(i > 0))).collect();
+
+        Self { coefficients }
+    }
+
+    /// Builder that collects coefficients one at a time into a buffer of `capacity`
+    /// coefficients, e.g. while they stream in from a device.
+    pub fn with_capacity(capacity: usize) -> ContinuedFractionBuilder {
+        ContinuedFractionBuilder {
+            coefficients: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Build a fraction from a known coefficient sequence `[a0; a1, a2, ...]`.
+    ///
+    /// Every coefficient after `a0` must be positive. A trailing 1 is accepted as is, see
+    /// [`normalize`](Self::normalize) for the canonical form.
+    ///
+    /// Coefficients sharing a common factor are not a redundant encoding: apart from the
+    /// trailing 1 every rational has exactly one expansion, so dividing them by their
+    /// [`gcd_slice`] always changes the value (`[2; 2] = 5/2` but `[1; 1] = 2`).
+    pub fn from_coefficients(coefficients: &[i32]) -> Result<Self, InvalidCoefficients> {
+        validate_coefficients(coefficients)?;
+
+        Ok(Self {
+            coefficients: coefficients.to_vec(),
+        })
+    }
+
+    /// Expand a floating-point value into at most `max_terms` coefficients.
+    ///
+    /// Expansion stops early once the remainder is exactly zero or the next coefficient
+    /// would not fit in an `i32`. Panics if `max_terms == 0`, if `x` is NaN or infinite, or
+    /// if `floor(x)` does not fit in an `i32`. Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn from_f64(mut x: f64, max_terms: usize) -> Self {
+        assert!(max_terms > 0, "from_f64 needs at least one term");
+        assert!(x.is_finite(), "cannot expand non-finite value {}", x);
+        assert!(
+            x.floor() >= i32::MIN as f64 && x.floor() <= i32::MAX as f64,
+            "integer part of {} does not fit in i32",
+            x
+        );
+
+        let mut coefficients = Vec::new();
+
+        while coefficients.len() < max_terms {
+            let a = x.floor();
+            coefficients.push(a as i32);
+
+            let fract = x - a;
+            if fract == 0.0 {
+                break;
+            }
+
+            x = 1.0 / fract;
+            if x >= i32::MAX as f64 {
+                break;
+            }
         }
 
         Self { coefficients }
@@ -46,32 +653,142 @@ impl ContinuedFraction {
         let mut den = 1;
 
         for &coef in self.coefficients.iter().rev().skip(1) {
-            (num, den) = (coef * num + den, num);
+            // coef * num may leave i32 even when the sum fits, as for i32::MIN / 3
+            (num, den) = ((coef as i64 * num as i64 + den as i64) as i32, num);
+        }
+
+        debug_assert_eq!(
+            (num as i64, den as i64),
+            self.to_rational_i64(),
+            "to_rational disagrees with to_rational_i64"
+        );
+
+        (num, den)
+    }
+
+    /// Like [`to_rational`](Self::to_rational), but returns `None` if any step overflows `i32`.
+    pub fn to_rational_checked(&self) -> Option<(i32, i32)> {
+        let Some(&last) = self.coefficients.last() else {
+            return Some((0, 1));
+        };
+
+        let (mut num, mut den) = (last, 1);
+
+        for &coef in self.coefficients.iter().rev().skip(1) {
+            (num, den) = (coef.checked_mul(num)?.checked_add(den)?, num);
+        }
+
+        Some((num, den))
+    }
+
+    /// Like [`to_rational`](Self::to_rational), but computed in 64-bit arithmetic.
+    pub fn to_rational_i64(&self) -> (i64, i64) {
+        let Some(&last) = self.coefficients.last() else {
+            return (0, 1);
+        };
+
+        let (mut num, mut den) = (last as i64, 1);
+
+        for &coef in self.coefficients.iter().rev().skip(1) {
+            (num, den) = (coef as i64 * num + den, num);
         }
 
         (num, den)
     }
 
+    /// Evaluate the continued fraction in floating point.
+    ///
+    /// The empty fraction is 0, as in [`to_rational`](Self::to_rational) and `Display`.
+    pub fn to_f64(&self) -> f64 {
+        let Some(&last) = self.coefficients.last() else {
+            return 0.0;
+        };
+
+        let mut x = last as f64;
+
+        for &coef in self.coefficients.iter().rev().skip(1) {
+            x = coef as f64 + 1.0 / x;
+        }
+
+        x
+    }
+
     /// Get the continued fraction coefficients [a0; a1, a2, ...]
     pub fn coefficients(&self) -> &[i32] {
         &self.coefficients
     }
 
+    /// Number of coefficients, as stored and not normalized.
+    pub fn depth(&self) -> usize {
+        self.coefficients.len()
+    }
+
     /// Compute convergents pairs (h_n, k_n) where h_n/k_n approaches the original value.
     pub fn convergents(&self) -> Vec<(i32, i32)> {
+        self.convergents_iter().collect()
+    }
+
+    /// Lazy version of [`convergents`](Self::convergents).
+    pub fn convergents_iter(&self) -> ConvergentsIter<'_> {
+        ConvergentsIter {
+            coefficients: self.coefficients.iter(),
+            h_2: 0,
+            k_2: 1,
+            h_1: 1,
+            k_1: 0,
+        }
+    }
+
+    /// Like [`convergents`](Self::convergents), but each entry is `None` once the recurrence
+    /// overflows `i32`; every later convergent depends on it, so they are `None` too.
+    pub fn convergents_checked(&self) -> Vec<Option<(i32, i32)>> {
         let mut result = Vec::new();
 
-        // h_{-2} = 0, h_{-1} = 1
-        // k_{-2} = 1, k_{-1} = 0
-        // h_n = a_n * h_{n-1} + h_{n-2}
-        // k_n = a_n * k_{n-1} + k_{n-2}
+        let mut state = Some(((0, 1), (1, 0))); // (h_{n-2}, k_{n-2}), (h_{n-1}, k_{n-1})
+
+        for &a in &self.coefficients {
+            state = state.and_then(|((h_2, k_2), (h_1, k_1))| {
+                let h = a.checked_mul(h_1)?.checked_add(h_2)?;
+                let k = a.checked_mul(k_1)?.checked_add(k_2)?;
+
+                Some(((h_1, k_1), (h, k)))
+            });
+
+            result.push(state.map(|(_, convergent)| convergent));
+        }
+
+        result
+    }
+
+    /// The `n`-th convergent `h_n/k_n` in `O(n)`, without collecting the earlier ones.
+    ///
+    /// `None` if `n` is out of range or the recurrence overflows `i32` on the way.
+    pub fn convergent_at(&self, n: usize) -> Option<(i32, i32)> {
+        let coefficients = self.coefficients.get(..=n)?;
+
+        let ((_, _), convergent) = coefficients.iter().try_fold(
+            ((0, 1), (1, 0)), // (h_{n-2}, k_{n-2}), (h_{n-1}, k_{n-1})
+            |((h_2, k_2), (h_1, k_1)), &a| {
+                let h = a.checked_mul(h_1)?.checked_add(h_2)?;
+                let k = a.checked_mul(k_1)?.checked_add(k_2)?;
+
+                Some(((h_1, k_1), (h, k)))
+            },
+        )?;
+
+        Some(convergent)
+    }
+
+    /// Like [`convergents`](Self::convergents), but computed in 64-bit arithmetic.
+    pub fn convergents_i64(&self) -> Vec<(i64, i64)> {
+        let mut result = Vec::new();
 
         let (mut h_2, mut k_2) = (0, 1); // h_{n-2}, k_{n-2}
         let (mut h_1, mut k_1) = (1, 0); // h_{n-1}, k_{n-1}
 
         for &a in &self.coefficients {
-            let h = a * h_1 + h_2;
-            let k = a * k_1 + k_2;
+            let h = a as i64 * h_1 + h_2;
+            let k = a as i64 * k_1 + k_2;
 
             result.push((h, k));
 
@@ -81,43 +798,5301 @@ impl ContinuedFraction {
 
         result
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Largest denominator `k_n` among the convergents before the recurrence overflows
+    /// `i32`, or 0 for the empty fraction. Numerators are not checked.
+    pub fn max_denominator_in_convergents(&self) -> i32 {
+        let (mut k_2, mut k_1) = (1i32, 0i32); // k_{n-2}, k_{n-1}
 
-    #[test]
-    fn test_gcd() {
-        assert_eq!(gcd(9, 3), 3);
-        assert_eq!(gcd(48, 18), 6);
-        assert_eq!(gcd(7, 7), 7);
-        assert_eq!(gcd(100, 25), 25);
-        assert_eq!(gcd(17, 13), 1);
+        for &a in &self.coefficients {
+            let Some(k) = a.checked_mul(k_1).and_then(|k| k.checked_add(k_2)) else {
+                break;
+            };
+
+            (k_2, k_1) = (k_1, k);
+        }
+
+        k_1
     }
 
-    #[test]
-    fn test_continued_fraction() {
-        let cf = ContinuedFraction::from_rational(3, 1);
-        assert_eq!(cf.coefficients(), &[3]);
+    /// Whether every denominator satisfies Lamé's bound `k_n >= F_{n+1}`, the Fibonacci
+    /// numbers being the slowest possible growth, reached when all `a_i` after `a0` are 1.
+    ///
+    /// Holds whenever the coefficients after `a0` are positive, so `false` flags a
+    /// coefficient sequence that breaks that invariant.
+    pub fn convergent_denominators_fibonacci_bound(&self) -> bool {
+        // Saturate instead of overflowing, k_n >= F_{n+1} still holds at the cap
+        let (mut k_2, mut k_1) = (1i128, 0i128); // k_{n-2}, k_{n-1}
+        let (mut f_1, mut f) = (0i128, 1i128); // F_n, F_{n+1}
 
-        let cf = ContinuedFraction::from_rational(1, 2);
-        assert_eq!(cf.coefficients(), &[0, 2]);
+        for &a in &self.coefficients {
+            let k = (a as i128).saturating_mul(k_1).saturating_add(k_2);
 
+            if k < f {
+                return false;
+            }
+
+            (k_2, k_1) = (k_1, k);
+            (f_1, f) = (f, f.saturating_add(f_1));
+        }
+
+        true
+    }
+
+    /// Whether the `n`-th convergent under-approximates ([`Parity::Even`]) or
+    /// over-approximates ([`Parity::Odd`]) the value. The last convergent equals it.
+    ///
+    /// Panics if there is no `n`-th convergent.
+    pub fn convergent_parity(&self, n: usize) -> Parity {
+        assert!(
+            n < self.coefficients.len(),
+            "no convergent {} for a fraction of depth {}",
+            n,
+            self.coefficients.len()
+        );
+
+        if n.is_multiple_of(2) {
+            Parity::Even
+        } else {
+            Parity::Odd
+        }
+    }
+
+    /// Mediants of each pair of consecutive convergents `(h_{n-1} + h_n, k_{n-1} + k_n)`.
+    pub fn mediant_sequence(&self) -> Vec<(i32, i32)> {
+        self.convergents()
+            .windows(2)
+            .map(|w| mediant(w[0], w[1]))
+            .collect()
+    }
+
+    /// Like [`mediant_sequence`](Self::mediant_sequence), but entries are `None` when the
+    /// mediant or either of its convergents overflows `i32`.
+    pub fn mediant_sequence_checked(&self) -> Vec<Option<(i32, i32)>> {
+        self.convergents_checked()
+            .windows(2)
+            .map(|w| mediant_checked(w[0]?, w[1]?))
+            .collect()
+    }
+
+    /// Convergents interleaved with the semi-convergents between them, in increasing order
+    /// of value.
+    ///
+    /// After `h_0/k_0` come `(h_{n-2} + j h_{n-1}, k_{n-2} + j k_{n-1})` for `j = 1..=a_n`,
+    /// the last of which (`j = a_n`) is the convergent `h_n/k_n` itself. The list stops at
+    /// the first one that overflows `i32`, every later one has a larger denominator.
+    pub fn semi_convergents(&self) -> Vec<(i32, i32)> {
+        let mut result = Vec::new();
+
+        let (mut h_2, mut k_2) = (0i64, 1i64); // h_{n-2}, k_{n-2}
+        let (mut h_1, mut k_1) = (1i64, 0i64); // h_{n-1}, k_{n-1}
+
+        'terms: for (n, &a) in self.coefficients.iter().enumerate() {
+            let a = i64::from(a);
+
+            if n == 0 {
+                result.push((a as i32, 1));
+            } else {
+                for j in 1..=a {
+                    match (i32::try_from(h_2 + j * h_1), i32::try_from(k_2 + j * k_1)) {
+                        (Ok(h), Ok(k)) => result.push((h, k)),
+                        _ => break 'terms,
+                    }
+                }
+            }
+
+            // Fits in i32, the last semi-convergent above was this convergent
+            let h = a * h_1 + h_2;
+            let k = a * k_1 + k_2;
+
+            (h_2, k_2) = (h_1, k_1);
+            (h_1, k_1) = (h, k);
+        }
+
+        // Denominators are positive, so cross-multiplying preserves the order
+        result.sort_by(|&(p1, q1), &(p2, q2)| {
+            (i64::from(p1) * i64::from(q2)).cmp(&(i64::from(p2) * i64::from(q1)))
+        });
+
+        result
+    }
+
+    /// Closest rational `p/q` to this fraction with `0 < q <= max_denom`, in lowest terms.
+    ///
+    /// Walks the convergents until the denominator exceeds `max_denom`, then picks between
+    /// the last convergent that fits and the largest semi-convergent towards the next one.
+    /// When both are equally close the convergent, which has the smaller denominator, wins.
+    pub fn best_approximation(&self, max_denom: i32) -> (i32, i32) {
+        assert!(
+            max_denom > 0,
+            "max_denom must be positive, got {}",
+            max_denom
+        );
+
+        let a = self.normalize().coefficients;
+        let max_denom = max_denom as i128;
+
+        let (mut h_2, mut k_2) = (0, 1); // h_{n-2}, k_{n-2}
+        let (mut h_1, mut k_1) = (1, 0); // h_{n-1}, k_{n-1}
+
+        for (n, &a_n) in a.iter().enumerate() {
+            let h = a_n as i128 * h_1 + h_2;
+            let k = a_n as i128 * k_1 + k_2;
+
+            if k > max_denom {
+                let j = (max_denom - k_2) / k_1;
+                let semi = (h_2 + j * h_1, k_2 + j * k_1);
+
+                let semi_is_closer = match (2 * j).cmp(&(a_n as i128)) {
+                    Ordering::Greater => true,
+                    Ordering::Less => false,
+                    Ordering::Equal => {
+                        // Half rule: the semi-convergent is closer iff
+                        // [0; a_{n+1}, ...] < k_{n-2}/k_{n-1} = [0; a_{n-1}, ..., a_1]
+                        let tail = Self {
+                            coefficients: [0].iter().chain(&a[n + 1..]).copied().collect(),
+                        };
+                        let reversed = Self {
+                            coefficients: [0].iter().chain(a[1..n].iter().rev()).copied().collect(),
+                        };
+
+                        tail < reversed
+                    }
+                };
+
+                let (p, q) = if semi_is_closer { semi } else { (h_1, k_1) };
+                return (
+                    i32::try_from(p).expect("best approximation overflowed i32"),
+                    q as i32,
+                );
+            }
+
+            (h_2, k_2) = (h_1, k_1);
+            (h_1, k_1) = (h, k);
+        }
+
+        (
+            i32::try_from(h_1).expect("best approximation overflowed i32"),
+            k_1 as i32,
+        )
+    }
+
+    /// Closest rational `p/q` to this fraction with `0 < q <= max_q`, found by walking down
+    /// the Stern-Brocot tree one mediant at a time.
+    ///
+    /// Agrees with [`best_approximation`](Self::best_approximation) except on ties, where
+    /// the smaller denominator wins and then the upper bound, like rounding half up. It
+    /// takes one step per unit of `a1 + a2 + ...`, so prefer `best_approximation`, which
+    /// takes one step per coefficient, unless the coefficients are known to be small.
+    pub fn reduce_to_denom(&self, max_q: i32) -> (i32, i32) {
+        assert!(max_q > 0, "max_q must be positive, got {}", max_q);
+
+        let (p, q) = self.to_rational_i64();
+        let (p, q, max_q) = (p as i128, q as i128, max_q as i128);
+
+        let a0 = floor_div(p, q);
+        let (mut lo, mut hi) = ((a0, 1), (a0 + 1, 1));
+
+        if a0 * q == p {
+            return (i32::try_from(a0).expect("approximation overflowed i32"), 1);
+        }
+
+        loop {
+            let m = (lo.0 + hi.0, lo.1 + hi.1);
+            if m.1 > max_q {
+                break;
+            }
+
+            match (m.0 * q).cmp(&(p * m.1)) {
+                Ordering::Less => lo = m,
+                Ordering::Greater => hi = m,
+                Ordering::Equal => {
+                    lo = m;
+                    hi = m;
+                    break;
+                }
+            }
+        }
+
+        // Compare x - lo against hi - x, cross-multiplied by q * lo.1 * hi.1
+        let below = (p * lo.1 - lo.0 * q) * hi.1;
+        let above = (hi.0 * q - p * hi.1) * lo.1;
+
+        let (r, s) = match below.cmp(&above) {
+            Ordering::Less => lo,
+            Ordering::Greater => hi,
+            Ordering::Equal if lo.1 < hi.1 => lo,
+            Ordering::Equal => hi,
+        };
+
+        (
+            i32::try_from(r).expect("approximation overflowed i32"),
+            s as i32,
+        )
+    }
+
+    /// Simplest fraction `p/q`, the one with the smallest denominator, strictly between
+    /// `lo` and `hi`; `0` if the interval contains it. Panics unless `lo < hi`.
+    ///
+    /// Expands both bounds in lockstep: while they share the integer part `a` the answer
+    /// does too, and the search continues on the reciprocals of the fractional parts.
+    pub fn simplest_in_interval(lo: (i32, i32), hi: (i32, i32)) -> (i32, i32) {
+        let (lo, hi) = (
+            Self::from_rational(lo.0, lo.1),
+            Self::from_rational(hi.0, hi.1),
+        );
+        assert!(lo < hi, "empty interval ({}, {})", lo, hi);
+
+        if lo < Self::zero() && Self::zero() < hi {
+            return (0, 1);
+        }
+
+        // Mirror negative intervals onto the positive side
+        if hi <= Self::zero() {
+            let (p, q) = Self::simplest_in_interval((-hi).to_rational(), (-lo).to_rational());
+            return (-p, q);
+        }
+
+        let (a, b) = lo.to_rational_i64();
+        let (c, d) = hi.to_rational_i64();
+        let (mut lo, mut hi) = ((a as i128, b as i128), Some((c as i128, d as i128)));
+        let mut coefficients = Vec::new();
+
+        loop {
+            let a = floor_div(lo.0, lo.1);
+
+            // Stop as soon as an integer fits strictly inside
+            let (c, d) = match hi {
+                Some((c, d)) if (a + 1) * d >= c => (c, d),
+                _ => {
+                    coefficients.push(a + 1);
+                    break;
+                }
+            };
+            coefficients.push(a);
+
+            // 1/(hi - a) < 1/(x - a) < 1/(lo - a), the upper end is unbounded when lo == a
+            let next_hi = (lo.0 != a * lo.1).then(|| (lo.1, lo.0 - a * lo.1));
+            (lo, hi) = ((d, c - a * d), next_hi);
+        }
+
+        let coefficients: Vec<i32> = coefficients
+            .into_iter()
+            .map(|a| i32::try_from(a).expect("coefficient overflowed i32"))
+            .collect();
+
+        Self { coefficients }.to_rational()
+    }
+
+    /// Simplest fraction `p/q`, the one with the smallest denominator, with
+    /// `|x - p/q| <= epsilon`.
+    ///
+    /// Descends the Stern-Brocot tree towards the interval, taking each run of steps in
+    /// the same direction at once, so it needs one iteration per coefficient rather than
+    /// per step.
+    pub fn rationalize(x: f64, epsilon: f64) -> Result<(i32, i32), RationalizeError> {
+        if !x.is_finite() || !epsilon.is_finite() {
+            return Err(RationalizeError::NotFinite);
+        }
+        if epsilon < 0.0 {
+            return Err(RationalizeError::NegativeEpsilon);
+        }
+
+        let (lo, hi) = (x - epsilon, x + epsilon);
+        if lo <= 0.0 && 0.0 <= hi {
+            return Ok((0, 1));
+        }
+
+        // By symmetry only positive intervals need a search
+        let (lo, hi, sign) = if hi < 0.0 {
+            (-hi, -lo, -1)
+        } else {
+            (lo, hi, 1)
+        };
+
+        let value = |(p, q): (i64, i64)| p as f64 / q as f64;
+        let (mut left, mut right) = ((0i64, 1i64), (1i64, 0i64));
+
+        loop {
+            let mediant = (left.0 + right.0, left.1 + right.1);
+            if mediant.0 > i32::MAX as i64 || mediant.1 > i32::MAX as i64 {
+                return Err(RationalizeError::Overflow);
+            }
+
+            let m = value(mediant);
+            if lo <= m && m <= hi {
+                return Ok((sign * mediant.0 as i32, mediant.1 as i32));
+            }
+
+            // Largest k keeping (left + k*right) below lo, or (right + k*left) above hi,
+            // estimated in floating point and corrected by at most a step
+            let (from, towards, target) = if m < lo {
+                (left, right, lo)
+            } else {
+                (right, left, hi)
+            };
+
+            let k = (target * from.1 as f64 - from.0 as f64)
+                / (towards.0 as f64 - target * towards.1 as f64);
+            if k.is_nan() || k >= i32::MAX as f64 {
+                return Err(RationalizeError::Overflow);
+            }
+
+            let step = |k: i64| (from.0 + k * towards.0, from.1 + k * towards.1);
+            let outside = |r: (i64, i64)| {
+                if m < lo { value(r) < lo } else { value(r) > hi }
+            };
+
+            let mut k = (k as i64).max(1);
+            while k > 1 && !outside(step(k)) {
+                k -= 1;
+            }
+
+            if m < lo {
+                left = step(k);
+            } else {
+                right = step(k);
+            }
+        }
+    }
+
+    /// `[L/M]` Padé approximant `P/Q` of the power series `s0 + s1*x + s2*x^2 + ...`, with
+    /// `deg P <= L`, `deg Q <= M` and `Q*S - P = O(x^(L+M+1))`. Coefficients run from the
+    /// constant term up; missing series terms count as zero.
+    ///
+    /// Runs the extended Euclidean algorithm on `x^(L+M+1)` and the truncated series,
+    /// stopping at the first remainder of degree at most `L`, the polynomial analogue of
+    /// stopping at a convergent. The result is scaled to integer coefficients without a
+    /// common factor and with `Q(0) > 0`. `None` if `Q(0)` would be zero, i.e. the
+    /// approximant is not defined, or if a coefficient overflows.
+    pub fn pade_approximant(
+        series: &[i32],
+        numerator_degree: usize,
+        denominator_degree: usize,
+    ) -> Option<(Vec<i32>, Vec<i32>)> {
+        let n = numerator_degree + denominator_degree + 1;
+
+        // Invariant for both pairs: r == t * S (mod x^n)
+        let mut r0 = vec![0i128; n];
+        r0.push(1);
+        let mut t0 = Vec::new();
+
+        let mut r1 = series.iter().take(n).map(|&a| a as i128).collect();
+        poly_trim(&mut r1);
+        let mut t1 = vec![1i128];
+
+        while r1.len() > numerator_degree + 1 {
+            let (r2, t2) = poly_euclid_step(&r0, &t0, &r1, &t1)?;
+            (r0, t0) = (r1, t1);
+            (r1, t1) = (r2, t2);
+        }
+
+        let q0 = t1[0];
+        if q0 == 0 {
+            return None;
+        }
+
+        let content = r1.iter().chain(&t1).fold(0, |g, &c| gcd_i128(g, c.abs()));
+        let normalize = |poly: &[i128]| {
+            poly.iter()
+                .map(|&c| i32::try_from(c / content * q0.signum()).ok())
+                .collect::<Option<Vec<i32>>>()
+        };
+
+        // The zero numerator is written as [0]
+        let numerator = if r1.is_empty() {
+            vec![0]
+        } else {
+            normalize(&r1)?
+        };
+        Some((numerator, normalize(&t1)?))
+    }
+
+    /// Distinct gap lengths, in increasing order, between the points `{k*x}` for
+    /// `k = 1..=n` on the unit circle. By the three-distance theorem there are at most 3.
+    ///
+    /// Exact: with `x = p/q` the points are the residues `k*p mod q` over `q`, so the gaps
+    /// are compared as integers. The points repeat after `q` steps, so only the first
+    /// `min(n, q)` are placed. Empty for `n == 0`.
+    pub fn three_distance_theorem(&self, n: u32) -> Vec<f64> {
+        let (p, q) = self.to_rational_i64();
+        let p = i128::from(p.rem_euclid(q));
+
+        let mut points: Vec<i64> = (1..=i64::from(n).min(q))
+            .map(|k| (i128::from(k) * p % i128::from(q)) as i64)
+            .collect();
+        points.sort_unstable();
+        points.dedup();
+
+        let Some(&first) = points.first() else {
+            return Vec::new();
+        };
+
+        // The circle closes from the last point back round to the first
+        let mut gaps: Vec<i64> = points.windows(2).map(|w| w[1] - w[0]).collect();
+        gaps.push(first + q - points[points.len() - 1]);
+        gaps.sort_unstable();
+        gaps.dedup();
+
+        debug_assert!(gaps.len() <= 3, "three-distance theorem violated");
+
+        gaps.into_iter().map(|g| g as f64 / q as f64).collect()
+    }
+
+    /// Smallest `n >= 1` with `{n*x}` within `epsilon` of `{beta}` on the unit circle, as in
+    /// Kronecker's theorem. Requires the `std` feature.
+    ///
+    /// With `x = p/q` the multiples repeat with period `q`, so this is `0` when no `n <= q`
+    /// works. `q` may exceed `u32::MAX`, hence the `u64`. Panics if `beta` or `epsilon` is
+    /// NaN or infinite.
+    ///
+    /// The residues `n*p mod q` are searched with a Euclid-like recursion over the target
+    /// window instead of one multiple at a time.
+    #[cfg(feature = "std")]
+    pub fn kronecker_n(&self, beta: f64, epsilon: f64) -> u64 {
+        assert!(
+            beta.is_finite() && epsilon.is_finite(),
+            "kronecker_n needs finite inputs, got beta = {}, epsilon = {}",
+            beta,
+            epsilon
+        );
+
+        let (p, q) = self.normalize().to_rational_i64();
+        let (a, q) = (p.rem_euclid(q) as i128, q as i128);
+
+        // Integer residues r with |r/q - {beta}| < epsilon, possibly wrapping round past q
+        let centre = (beta - beta.floor()) * q as f64;
+        let lo = ((centre - epsilon * q as f64).floor() + 1.0) as i128;
+        let hi = ((centre + epsilon * q as f64).ceil() - 1.0) as i128;
+
+        if lo > hi {
+            return 0;
+        }
+        if hi - lo + 1 >= q {
+            return 1;
+        }
+
+        let ranges = if lo < 0 {
+            [(lo + q, q - 1), (0, hi)]
+        } else if hi >= q {
+            [(lo, q - 1), (0, hi - q)]
+        } else {
+            [(lo, hi), (lo, hi)]
+        };
+
+        // Residue 0 is reached at n = q, every other one at some 0 < n < q
+        let first = |(l, r): (i128, i128)| {
+            if l == 0 {
+                let sooner = if r >= 1 {
+                    first_multiple_in(a, q, 1, r)
+                } else {
+                    None
+                };
+                Some(sooner.unwrap_or(q))
+            } else {
+                first_multiple_in(a, q, l, r)
+            }
+        };
+
+        ranges
+            .into_iter()
+            .filter_map(first)
+            .min()
+            .map_or(0, |n| u64::try_from(n).expect("n <= q fits in u64"))
+    }
+
+    /// Largest rational `p/q` at or below this fraction with `0 < q <= max_denom`.
+    ///
+    /// Even-indexed convergents approach from below, so this is either the last of those
+    /// that fits or the largest semi-convergent leading to the next one.
+    pub fn best_lower_approximation(&self, max_denom: i32) -> (i32, i32) {
+        self.one_sided_approximation(max_denom, true)
+    }
+
+    /// Smallest rational `p/q` at or above this fraction with `0 < q <= max_denom`, from the
+    /// odd-indexed convergents and their semi-convergents.
+    pub fn best_upper_approximation(&self, max_denom: i32) -> (i32, i32) {
+        self.one_sided_approximation(max_denom, false)
+    }
+
+    /// Neighbours of the value in the Farey set of order `n`: the largest `p1/q1` strictly
+    /// below it and the smallest `p2/q2` strictly above it with `0 < q1, q2 <= n`.
+    /// Panics if `n == 0`.
+    ///
+    /// They are adjacent, `p2*q1 - p1*q2 == 1`, unless the value is itself in the set, in
+    /// which case each of them is adjacent to it instead.
+    ///
+    /// When the denominator exceeds `n` these are the one-sided best approximations,
+    /// otherwise they solve `a*q ≡ ±1 (mod b)` for the fraction `a/b` itself.
+    pub fn best_rational_in_farey_neighbors(&self, n: u32) -> ((i32, i32), (i32, i32)) {
+        assert!(n > 0, "Farey order must be positive");
+        let max_denom = i32::try_from(n).unwrap_or(i32::MAX);
+
+        let (a, b) = self.normalize().to_rational();
+        if b > max_denom {
+            return (
+                self.best_lower_approximation(max_denom),
+                self.best_upper_approximation(max_denom),
+            );
+        }
+
+        // a*q1 - p1*b == 1 and p2*b - a*q2 == 1, each with the largest q that fits
+        let r = mod_inverse(a, b).expect("to_rational is in lowest terms") as i64;
+        let (a, b, n) = (a as i64, b as i64, max_denom as i64);
+        let largest = |r: i64| r + (n - r) / b * b;
+
+        let q1 = largest(r);
+        let q2 = largest((b - r) % b);
+        let fit = |p: i64| i32::try_from(p).expect("Farey neighbour overflowed i32");
+
+        (
+            (fit((a * q1 - 1) / b), q1 as i32),
+            (fit((a * q2 + 1) / b), q2 as i32),
+        )
+    }
+
+    /// Shared walk for the one-sided best approximations.
+    fn one_sided_approximation(&self, max_denom: i32, below: bool) -> (i32, i32) {
+        assert!(
+            max_denom > 0,
+            "max_denom must be positive, got {}",
+            max_denom
+        );
+
+        let a = self.normalize().coefficients;
+        let max_denom = max_denom as i128;
+
+        let (mut h_2, mut k_2) = (0, 1); // h_{n-2}, k_{n-2}
+        let (mut h_1, mut k_1) = (1, 0); // h_{n-1}, k_{n-1}
+
+        for (n, &a_n) in a.iter().enumerate() {
+            let h = a_n as i128 * h_1 + h_2;
+            let k = a_n as i128 * k_1 + k_2;
+
+            if k > max_denom {
+                // The semi-convergents towards h_n/k_n lie on the same side as it does,
+                // while h_{n-1}/k_{n-1} lies on the other
+                let j = (max_denom - k_2) / k_1;
+                let (p, q) = if (n % 2 == 0) == below {
+                    (h_2 + j * h_1, k_2 + j * k_1)
+                } else {
+                    (h_1, k_1)
+                };
+
+                return (
+                    i32::try_from(p).expect("best approximation overflowed i32"),
+                    q as i32,
+                );
+            }
+
+            (h_2, k_2) = (h_1, k_1);
+            (h_1, k_1) = (h, k);
+        }
+
+        // Every convergent fits, the fraction itself is the answer
+        (
+            i32::try_from(h_1).expect("best approximation overflowed i32"),
+            k_1 as i32,
+        )
+    }
+
+    /// Exact distance `|x - y|` as `(p, q)` in lowest terms, panics if it does not fit in
+    /// an `i32`.
+    pub fn distance(&self, other: &Self) -> (i32, i32) {
+        self.distance_checked(other)
+            .expect("distance overflowed i32")
+    }
+
+    /// Like [`distance`](Self::distance), but returns `None` if the result does not fit in
+    /// an `i32`.
+    pub fn distance_checked(&self, other: &Self) -> Option<(i32, i32)> {
+        let (p1, q1) = self.to_rational_i64();
+        let (p2, q2) = other.to_rational_i64();
+
+        // Cross-multiply in i128 so only the reduced result has to fit
+        let num = (p1 as i128 * q2 as i128 - p2 as i128 * q1 as i128).abs();
+        let den = q1 as i128 * q2 as i128;
+        let d = gcd_i128(num, den);
+
+        Some((i32::try_from(num / d).ok()?, i32::try_from(den / d).ok()?))
+    }
+
+    /// Whether some convergent before the last satisfies Hurwitz's bound
+    /// `|x - h/k| < 1 / (sqrt(5) * k^2)`. The last one equals `x` and is not counted.
+    ///
+    /// Exact, the bound is squared to `5 * (|x*k - h| * k)^2 < 1`.
+    pub fn hurwitz_check(&self) -> bool {
+        let convergents = self.convergents_i64();
+        let Some((&(p, q), rest)) = convergents.split_last() else {
+            return false;
+        };
+
+        rest.iter().any(|&(h, k)| {
+            // |x*k - h| * k * q, q > 0 for validated coefficients
+            let scaled = (p as i128 * k as i128 - h as i128 * q as i128).abs() * k as i128;
+            5 * scaled * scaled < q as i128 * q as i128
+        })
+    }
+
+    /// Error `|x - h_n/k_n|` of the `n`-th convergent together with its bound
+    /// `1 / (k_n * k_{n+1})`.
+    ///
+    /// The bound is attained by the second to last convergent, and both are zero for the
+    /// last one. Panics if `n` is not a valid coefficient index.
+    pub fn approximation_error(&self, n: usize) -> (f64, f64) {
+        let convergents = self.convergents_i64();
+        assert!(
+            n < convergents.len(),
+            "convergent index {} out of range for {} coefficients",
+            n,
+            convergents.len()
+        );
+
+        let (p, q) = *convergents.last().unwrap();
+        let (h, k) = convergents[n];
+
+        // Cross-multiply so the error is exact until the final division
+        let diff = (p as i128 * k as i128 - h as i128 * q as i128).abs();
+        let error = diff as f64 / (q as f64 * k as f64);
+
+        let bound = match convergents.get(n + 1) {
+            Some(&(_, k_next)) => 1.0 / (k as f64 * k_next as f64),
+            None => 0.0,
+        };
+
+        (error, bound)
+    }
+
+    /// Complete quotient `[a_n; a_{n+1}, ...]`, panics if `n` is not a valid coefficient index.
+    pub fn tail(&self, n: usize) -> Self {
+        assert!(
+            n < self.coefficients.len(),
+            "tail index {} out of range for {} coefficients",
+            n,
+            self.coefficients.len()
+        );
+
+        Self {
+            coefficients: self.coefficients[n..].to_vec(),
+        }
+    }
+
+    /// Fraction formed by the first `n` coefficients, i.e. the `n`-th convergent.
+    ///
+    /// Returns a copy of the whole fraction when `n` exceeds its length, panics if `n == 0`.
+    pub fn truncate(&self, n: usize) -> Self {
+        assert!(n > 0, "truncate needs at least one coefficient");
+
+        let n = n.min(self.coefficients.len());
+
+        Self {
+            coefficients: self.coefficients[..n].to_vec(),
+        }
+    }
+
+    /// Reciprocal `1/x`, panics if the fraction is zero.
+    ///
+    /// Streamed through the homographic map `z = 1 / x`.
+    pub fn reciprocal(&self) -> Self {
+        self.linear_fractional(0, 1, 1, 0)
+    }
+
+    /// Whether the fraction is an integer, i.e. has a single coefficient in canonical form.
+    pub fn is_integer(&self) -> bool {
+        self.normalize().coefficients.len() == 1
+    }
+
+    /// Integer part `a0` of the canonical form, rounding towards negative infinity.
+    pub fn floor(&self) -> i32 {
+        self.normalize().coefficients[0]
+    }
+
+    /// Fractional part `x - floor(x)` as `[0; a1, a2, ...]`.
+    pub fn fract(&self) -> Self {
+        let mut coefficients = self.normalize().coefficients;
+        coefficients[0] = 0;
+
+        Self { coefficients }
+    }
+
+    /// Path from the root `1/1` of the Stern-Brocot tree down to this fraction.
+    ///
+    /// `[a0; a1, ..., an]` maps to `R^a0 L^a1 R^a2 ...` with the last run shortened by one,
+    /// so the path has `a0 + ... + an - 1` steps. Panics unless the fraction is positive.
+    pub fn stern_brocot_path(&self) -> Vec<Direction> {
+        let coefficients = self.normalize().coefficients;
+        assert!(
+            coefficients[0] >= 0 && coefficients != [0],
+            "only positive fractions are in the Stern-Brocot tree"
+        );
+
+        let last = coefficients.len() - 1;
+        let mut path = Vec::new();
+
+        for (i, &a) in coefficients.iter().enumerate() {
+            let direction = if i % 2 == 0 {
+                Direction::Right
+            } else {
+                Direction::Left
+            };
+            let run = if i == last { a - 1 } else { a };
+
+            path.extend((0..run).map(|_| direction));
+        }
+
+        path
+    }
+
+    /// Depth in the Stern-Brocot tree, the root `1/1` being at depth 0: the number of
+    /// steps in [`stern_brocot_path`](Self::stern_brocot_path), `a0 + ... + an - 1`, without
+    /// building the path. Panics unless the fraction is positive.
+    pub fn stern_brocot_depth(&self) -> u64 {
+        let coefficients = self.normalize().coefficients;
+        assert!(
+            coefficients[0] >= 0 && coefficients != [0],
+            "only positive fractions are in the Stern-Brocot tree"
+        );
+
+        coefficients.iter().map(|&a| a as u64).sum::<u64>() - 1
+    }
+
+    /// Inverse of [`stern_brocot_path`](Self::stern_brocot_path), the empty path is `1/1`.
+    pub fn from_stern_brocot_path(path: &[Direction]) -> Self {
+        // Run lengths alternate R, L, R, ... so a leading L means a0 == 0
+        let mut coefficients = vec![0];
+        let mut current = Direction::Right;
+
+        for &direction in path {
+            if direction != current {
+                coefficients.push(0);
+                current = direction;
+            }
+            *coefficients.last_mut().unwrap() += 1;
+        }
+        *coefficients.last_mut().unwrap() += 1;
+
+        Self { coefficients }
+    }
+
+    /// Pack the normalized coefficients into a `u64` tree address: the top 4 bits hold the
+    /// number of coefficients and the 15 nibbles below hold `a0, a1, ...` from the lowest.
+    ///
+    /// Panics unless there are at most 15 coefficients, each in `0..=15`.
+    pub fn encode_stern_brocot(&self) -> u64 {
+        let coefficients = self.normalize().coefficients;
+        assert!(
+            coefficients.len() <= 15,
+            "{} coefficients do not fit in a u64 address",
+            coefficients.len()
+        );
+
+        let mut code = (coefficients.len() as u64) << 60;
+
+        for (i, &a) in coefficients.iter().enumerate() {
+            assert!(
+                (0..=15).contains(&a),
+                "coefficient {} does not fit in a nibble",
+                a
+            );
+            code |= (a as u64) << (4 * i);
+        }
+
+        code
+    }
+
+    /// Inverse of [`encode_stern_brocot`](Self::encode_stern_brocot), panics if `code` is
+    /// not a valid address.
+    pub fn decode_stern_brocot(code: u64) -> Self {
+        let len = (code >> 60) as usize;
+        let coefficients: Vec<i32> = (0..len).map(|i| ((code >> (4 * i)) & 0xf) as i32).collect();
+
+        Self::from_coefficients(&coefficients).expect("invalid Stern-Brocot address")
+    }
+
+    /// LaTeX nested fraction, e.g. `3 + \cfrac{1}{7 + \cfrac{1}{15}}` for `[3; 7, 15]`.
+    ///
+    /// Uses the coefficients as stored, an integer renders as itself and the empty
+    /// fraction as `0`.
+    pub fn to_latex(&self) -> String {
+        self.nested(|a, inner| format!("{} + \\cfrac{{1}}{{{}}}", a, inner))
+    }
+
+    /// Plain text nested fraction, e.g. `3 + 1/(7 + 1/15)` for `[3; 7, 15]`.
+    pub fn to_inline_notation(&self) -> String {
+        self.nested(|a, inner| {
+            if inner.contains(' ') {
+                format!("{} + 1/({})", a, inner)
+            } else {
+                format!("{} + 1/{}", a, inner)
+            }
+        })
+    }
+
+    /// Decimal expansion truncated to `places` digits after the point, e.g. `"3.14285"` for
+    /// `22/7` with 5 places. Exact, uses long division on [`to_rational`](Self::to_rational).
+    pub fn to_decimal_string(&self, places: usize) -> String {
+        let (p, q) = self.to_rational();
+        let (p, q) = (p as i64, q as i64);
+
+        let mut decimal = String::new();
+        if p < 0 {
+            decimal.push('-');
+        }
+
+        let mut rem = p.abs() % q;
+        decimal.push_str(&(p.abs() / q).to_string());
+
+        if places > 0 {
+            decimal.push('.');
+        }
+
+        for _ in 0..places {
+            rem *= 10;
+            decimal.push(char::from(b'0' + (rem / q) as u8));
+            rem %= q;
+        }
+
+        decimal
+    }
+
+    /// Decimal expansion split into `(non_repeating, repeating)`, e.g. `("0.", "142857")` for
+    /// `1/7` and `("0.25", "")` for `1/4`. The repeating part is empty for a terminating decimal.
+    pub fn repeating_decimal_period(&self) -> (String, String) {
+        let (p, q) = self.to_rational();
+        let (p, q) = (p as i64, q as i64);
+
+        let mut prefix = String::new();
+        if p < 0 {
+            prefix.push('-');
+        }
+        prefix.push_str(&(p.abs() / q).to_string());
+
+        let mut rem = p.abs() % q;
+        if rem == 0 {
+            return (prefix, String::new());
+        }
+        prefix.push('.');
+
+        // Position in `digits` at which each remainder was first seen
+        let mut seen = BTreeMap::new();
+        let mut digits = String::new();
+
+        while rem != 0 {
+            if let Some(&start) = seen.get(&rem) {
+                prefix.push_str(&digits[..start]);
+                return (prefix, digits[start..].to_string());
+            }
+
+            seen.insert(rem, digits.len());
+            rem *= 10;
+            digits.push(char::from(b'0' + (rem / q) as u8));
+            rem %= q;
+        }
+
+        prefix.push_str(&digits);
+        (prefix, String::new())
+    }
+
+    /// Fold the coefficients from the innermost one outwards with `wrap(a_i, inner)`.
+    fn nested(&self, wrap: impl Fn(i32, &str) -> String) -> String {
+        let Some((&last, rest)) = self.coefficients.split_last() else {
+            return "0".to_string();
+        };
+
+        rest.iter()
+            .rev()
+            .fold(last.to_string(), |inner, &a| wrap(a, &inner))
+    }
+
+    /// Denominators of the greedy (Fibonacci-Sylvester) Egyptian fraction expansion
+    /// `p/q = 1/n1 + 1/n2 + ...`, strictly increasing.
+    ///
+    /// Empty for zero. Panics unless `0 <= p/q < 1`, or if a denominator overflows `i32`.
+    pub fn egyptian_fraction(&self) -> Vec<i32> {
+        let (p, q) = self.to_rational_i64();
+        assert!(
+            p >= 0 && p < q,
+            "egyptian fraction needs 0 <= p/q < 1, got {}/{}",
+            p,
+            q
+        );
+
+        let (mut p, mut q) = (p as i128, q as i128);
+        let mut denominators = Vec::new();
+
+        while p != 0 {
+            // Largest unit fraction not exceeding p/q
+            let n = (q + p - 1) / p;
+            denominators.push(i32::try_from(n).expect("egyptian denominator overflowed i32"));
+
+            (p, q) = (p * n - q, q * n);
+            let d = gcd_i128(p, q);
+            (p, q) = (p / d, q / d);
+        }
+
+        denominators
+    }
+
+    /// Denominators of the binary Egyptian fraction expansion, strictly increasing.
+    ///
+    /// With `2^k >= q` and `p * 2^k = a*q + r`, each set bit `2^i` of `a` gives `1/2^(k-i)`
+    /// and each set bit `2^j` of `r` gives `1/(q * 2^(k-j))`. At most `2k` terms, and every
+    /// denominator is below `2q^2`, unlike the greedy [`egyptian_fraction`](Self::egyptian_fraction).
+    ///
+    /// Empty for zero. Panics unless `0 <= p/q < 1`, or if a denominator overflows `i32`.
+    pub fn to_egyptian_binary(&self) -> Vec<i32> {
+        let (p, q) = self.to_rational_i64();
+        assert!(
+            p >= 0 && p < q,
+            "egyptian fraction needs 0 <= p/q < 1, got {}/{}",
+            p,
+            q
+        );
+
+        let k = (q as u64).next_power_of_two().trailing_zeros();
+        let (a, r) = ((p << k) / q, (p << k) % q);
+
+        // Powers of two and multiples of q, which is not a power of two when r != 0
+        let mut denominators: Vec<i64> = (0..k)
+            .filter(|i| a >> i & 1 == 1)
+            .map(|i| 1 << (k - i))
+            .chain((0..k).filter(|j| r >> j & 1 == 1).map(|j| q << (k - j)))
+            .collect();
+        denominators.sort_unstable();
+
+        denominators
+            .into_iter()
+            .map(|n| i32::try_from(n).expect("egyptian denominator overflowed i32"))
+            .collect()
+    }
+
+    /// Geometric mean `(a1 * a2 * ... * an)^(1/n)` of the coefficients after `a0`, in
+    /// normalized form. NaN for integers. Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn khinchin_geometric_mean(&self) -> f64 {
+        let coefficients = self.normalize().coefficients;
+        let tail = &coefficients[1..];
+
+        if tail.is_empty() {
+            return f64::NAN;
+        }
+
+        // Sum logarithms instead of multiplying, the product overflows quickly
+        let log_sum: f64 = tail.iter().map(|&a| (a as f64).ln()).sum();
+        (log_sum / tail.len() as f64).exp()
+    }
+
+    /// Whether [`khinchin_geometric_mean`](Self::khinchin_geometric_mean) is within 10% of
+    /// Khinchin's constant, as expected for almost every real number.
+    #[cfg(feature = "std")]
+    pub fn khinchin_check(&self) -> bool {
+        const KHINCHIN: f64 = 2.685_452_001_065_306;
+
+        (self.khinchin_geometric_mean() - KHINCHIN).abs() <= 0.1 * KHINCHIN
+    }
+
+    /// Mean, population variance and geometric mean of the coefficients after `a0`, in
+    /// normalized form. All NaN for integers. Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn partial_quotient_statistics(&self) -> (f64, f64, f64) {
+        let coefficients = self.normalize().coefficients;
+        let tail = &coefficients[1..];
+
+        if tail.is_empty() {
+            return (f64::NAN, f64::NAN, f64::NAN);
+        }
+
+        let n = tail.len() as f64;
+        let mean = tail.iter().map(|&a| a as f64).sum::<f64>() / n;
+        let variance = tail.iter().map(|&a| (a as f64 - mean).powi(2)).sum::<f64>() / n;
+
+        (mean, variance, self.khinchin_geometric_mean())
+    }
+
+    /// Sum of the coefficients as stored, 0 for the empty fraction.
+    pub fn coefficient_sum(&self) -> i64 {
+        self.coefficients.iter().map(|&a| a as i64).sum()
+    }
+
+    /// Product of the coefficients as stored, 1 for the empty fraction. Panics if it
+    /// overflows `i64`.
+    pub fn coefficient_product(&self) -> i64 {
+        self.coefficients
+            .iter()
+            .try_fold(1i64, |product, &a| product.checked_mul(a as i64))
+            .expect("coefficient product overflowed i64")
+    }
+
+    /// Empirical frequency of each value `k` among the coefficients after `a0`, in
+    /// normalized form, sorted by `k`. Empty for integers.
+    ///
+    /// Compare against [`gauss_kuzmin_probability`](Self::gauss_kuzmin_probability), the
+    /// limiting frequency for almost every real number.
+    pub fn gauss_kuzmin_distribution(&self) -> Vec<(i32, f64)> {
+        let coefficients = self.normalize().coefficients;
+        let tail = &coefficients[1..];
+
+        let mut counts = BTreeMap::new();
+        for &a in tail {
+            *counts.entry(a).or_insert(0usize) += 1;
+        }
+
+        counts
+            .into_iter()
+            .map(|(k, count)| (k, count as f64 / tail.len() as f64))
+            .collect()
+    }
+
+    /// Gauss-Kuzmin probability `log2(1 + 1/(k*(k+2)))` of a coefficient equal to `k >= 1`.
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn gauss_kuzmin_probability(k: i32) -> f64 {
+        assert!(k > 0, "coefficients after a0 are positive, got {}", k);
+
+        let k = k as f64;
+        (1.0 + 1.0 / (k * (k + 2.0))).log2()
+    }
+
+    /// Product of the matrices `[[a_i, 1], [1, 0]]` over all coefficients, which equals
+    /// `[[h_n, h_{n-1}], [k_n, k_{n-1}]]`.
+    pub fn convergent_matrix(&self) -> ConvMatrix {
+        self.coefficients
+            .iter()
+            .fold(ConvMatrix::IDENTITY, |m, &a| m * ConvMatrix::coefficient(a))
+    }
+
+    /// Whether every stored coefficient is 1, i.e. this is a truncation of [`phi`](Self::phi).
+    ///
+    /// Looks at the coefficients as stored: the normalized form `[1; 1, ..., 2]` of the
+    /// same value does not count.
+    pub fn is_golden_ratio_approx(&self) -> bool {
+        !self.coefficients.is_empty() && self.coefficients.iter().all(|&a| a == 1)
+    }
+
+    /// Coefficients of `self` followed by those of `other`, as stored. Not addition: the
+    /// `a0` of `other` becomes an inner coefficient, so it must be positive.
+    pub fn concat(&self, other: &Self) -> Self {
+        if let Some(&a0) = other.coefficients.first() {
+            assert!(
+                a0 > 0 || self.coefficients.is_empty(),
+                "cannot append a fraction starting with {}",
+                a0
+            );
+        }
+
+        Self {
+            coefficients: [self.coefficients.as_slice(), &other.coefficients].concat(),
+        }
+    }
+
+    /// Coefficients repeated `n` times, like [`str::repeat`]; `repeat(0)` is zero.
+    ///
+    /// Panics if `n > 1` and `a0` is not positive, see [`concat`](Self::concat).
+    pub fn repeat(&self, n: usize) -> Self {
+        if n == 0 {
+            return Self::zero();
+        }
+
+        if let Some(&a0) = self.coefficients.first() {
+            assert!(
+                n == 1 || a0 > 0,
+                "cannot repeat a fraction starting with {}",
+                a0
+            );
+        }
+
+        Self {
+            coefficients: self.coefficients.repeat(n),
+        }
+    }
+
+    /// Length of the shortest block that repeats throughout `[a1, a2, ...]`, as stored.
+    ///
+    /// Only the stored coefficients are inspected, so the block must occur twice in full:
+    /// a lone block cannot be told apart from a rational, `[1; 2]` is both one period of
+    /// `sqrt(2)` and `3/2`. In particular the output of [`from_sqrt`](Self::from_sqrt), which
+    /// stores a single period, gives `None`; use the period length it returns instead. Any
+    /// further repetition may be cut short.
+    pub fn period(&self) -> Option<usize> {
+        let tail = self.coefficients.get(1..)?;
+
+        (1..=tail.len() / 2).find(|&p| tail.iter().zip(&tail[p..]).all(|(a, b)| a == b))
+    }
+
+    /// Exact comparison with `p/q`, panics if `q == 0`.
+    ///
+    /// Walks the convergents without building the value: even-indexed ones lie below the
+    /// fraction and odd-indexed ones above, so the first convergent that `p/q` does not
+    /// fall beyond decides the order.
+    pub fn compare_to_rational(&self, p: i32, q: i32) -> Ordering {
+        assert!(q != 0, "denominator must be non-zero, got {}/0", p);
+
+        let (p, q) = if q < 0 {
+            (-(p as i128), -(q as i128))
+        } else {
+            (p as i128, q as i128)
+        };
+
+        let (mut h_1, mut h_2, mut k_1, mut k_2) = (1i128, 0i128, 0i128, 1i128);
+        let last = self.coefficients.len().saturating_sub(1);
+
+        for (n, &a) in self.coefficients.iter().enumerate() {
+            let a = a as i128;
+            (h_1, h_2, k_1, k_2) = (a * h_1 + h_2, h_1, a * k_1 + k_2, k_1);
+
+            // Sign of h_n/k_n - p/q, k_n and q are both positive
+            let convergent = (h_1 * q).cmp(&(p * k_1));
+
+            if n == last {
+                return convergent;
+            }
+
+            match (n.is_multiple_of(2), convergent) {
+                (true, Ordering::Greater | Ordering::Equal) => return Ordering::Greater,
+                (false, Ordering::Less | Ordering::Equal) => return Ordering::Less,
+                _ => {}
+            }
+        }
+
+        // The empty fraction is 0, like `[0]`
+        0.cmp(&p)
+    }
+
+    /// Whether the fraction lies between `lo = p1/q1` and `hi = p2/q2`, either the closed
+    /// interval when `inclusive` or the open one otherwise.
+    ///
+    /// Exact, the bounds are expanded and compared as continued fractions.
+    pub fn in_interval(&self, lo: (i32, i32), hi: (i32, i32), inclusive: bool) -> bool {
+        let lo = Self::from_rational(lo.0, lo.1);
+        let hi = Self::from_rational(hi.0, hi.1);
+
+        if inclusive {
+            lo <= *self && *self <= hi
+        } else {
+            lo < *self && *self < hi
+        }
+    }
+
+    /// Möbius transformation `(a*x + b) / (c*x + d)`, streamed over the coefficients
+    /// with Gosper's unary algorithm. Panics if `c*x + d` is zero.
+    pub fn linear_fractional(&self, a: i32, b: i32, c: i32, d: i32) -> Self {
+        homographic(
+            &self.normalize().coefficients,
+            [a as i128, b as i128, c as i128, d as i128],
+        )
+    }
+
+    /// Sum `x + k`, streamed through the homographic map `z = (x + k) / 1`.
+    pub fn add_integer(&self, k: i32) -> Self {
+        self.linear_fractional(1, k, 0, 1)
+    }
+
+    /// Product `k * x`, streamed through the unary homographic map `z = k*x / 1` rather
+    /// than the two-input algorithm used by `Mul`.
+    pub fn scale(&self, k: i32) -> Self {
+        self.linear_fractional(k, 0, 0, 1)
+    }
+
+    /// Iterate over the coefficients [a0; a1, a2, ...] front to back.
+    pub fn iter(&self) -> ContinuedFractionIter<'_> {
+        ContinuedFractionIter {
+            coefficients: &self.coefficients,
+            front: 0,
+            back: self.coefficients.len(),
+        }
+    }
+
+    /// Canonical (shorter) form: the empty fraction becomes `[0]`, and a trailing 1 is
+    /// folded into the previous coefficient since `[..., a_n, 1] == [..., a_n + 1]`.
+    pub fn normalize(&self) -> Self {
+        let mut coefficients = self.coefficients.clone();
+        canonicalize(&mut coefficients);
+
+        Self { coefficients }
+    }
+
+    /// Whether the coefficients are already in the form returned by [`normalize`](Self::normalize).
+    pub fn is_normalized(&self) -> bool {
+        !matches!(self.coefficients.as_slice(), [] | [.., _, 1])
+    }
+}
+
+/// `(p, q)` as `p/q`, panics like [`ContinuedFraction::from_rational`].
+///
+/// There is no separate `TryFrom<(i32, i32)>`, it would conflict with the blanket impl
+/// derived from this one; use [`ContinuedFraction::try_from_rational`] instead.
+impl From<(i32, i32)> for ContinuedFraction {
+    fn from((p, q): (i32, i32)) -> Self {
+        Self::from_rational(p, q)
+    }
+}
+
+/// `n/m`, panics like [`ContinuedFraction::from_rational`].
+impl From<RatPair> for ContinuedFraction {
+    fn from(pair: RatPair) -> Self {
+        Self::from_rational(pair.n, pair.m)
+    }
+}
+
+/// `p/q` from the raw numerator and denominator, panics like
+/// [`ContinuedFraction::from_rational`]. Requires the `num-rational` feature.
+#[cfg(feature = "num-rational")]
+impl From<num_rational::Ratio<i32>> for ContinuedFraction {
+    fn from(ratio: num_rational::Ratio<i32>) -> Self {
+        let (p, q) = ratio.into_raw();
+        Self::from_rational(p, q)
+    }
+}
+
+/// Reduced ratio from [`ContinuedFraction::to_rational`]. Requires the `num-rational` feature.
+#[cfg(feature = "num-rational")]
+impl From<ContinuedFraction> for num_rational::Ratio<i32> {
+    fn from(cf: ContinuedFraction) -> Self {
+        let (p, q) = cf.normalize().to_rational();
+        Self::new(p, q)
+    }
+}
+
+/// Same as [`ContinuedFraction::to_rational`].
+impl From<ContinuedFraction> for (i32, i32) {
+    fn from(cf: ContinuedFraction) -> Self {
+        cf.to_rational()
+    }
+}
+
+/// Same as [`ContinuedFraction::to_rational`], without consuming the fraction.
+impl From<&ContinuedFraction> for (i32, i32) {
+    fn from(cf: &ContinuedFraction) -> Self {
+        cf.to_rational()
+    }
+}
+
+/// Same as [`ContinuedFraction::to_f64`].
+impl From<ContinuedFraction> for f64 {
+    fn from(cf: ContinuedFraction) -> Self {
+        cf.to_f64()
+    }
+}
+
+/// [`ContinuedFraction::to_f64`] rounded to single precision.
+impl From<ContinuedFraction> for f32 {
+    fn from(cf: ContinuedFraction) -> Self {
+        cf.to_f64() as f32
+    }
+}
+
+/// [`ContinuedFraction::from_f64`] with at most 20 terms, but returns
+/// [`ContinuedFractionError::InvalidInput`] instead of panicking. Requires the `std` feature.
+#[cfg(feature = "std")]
+impl TryFrom<f64> for ContinuedFraction {
+    type Error = ContinuedFractionError;
+
+    fn try_from(x: f64) -> Result<Self, Self::Error> {
+        if !x.is_finite() || x.floor() < i32::MIN as f64 || x.floor() > i32::MAX as f64 {
+            return Err(ContinuedFractionError::InvalidInput(format!(
+                "cannot expand {} into i32 coefficients",
+                x
+            )));
+        }
+
+        Ok(Self::from_f64(x, 20))
+    }
+}
+
+/// Validates like [`ContinuedFraction::from_coefficients`], but takes ownership of the
+/// vector instead of copying it.
+///
+/// There is no `From<Vec<i32>>`, it would conflict with the blanket `TryFrom` impl.
+impl TryFrom<Vec<i32>> for ContinuedFraction {
+    type Error = InvalidCoefficients;
+
+    fn try_from(coefficients: Vec<i32>) -> Result<Self, Self::Error> {
+        validate_coefficients(&coefficients)?;
+
+        Ok(Self { coefficients })
+    }
+}
+
+/// The integer `n` as the single-coefficient fraction `[n]`.
+impl From<i32> for ContinuedFraction {
+    fn from(n: i32) -> Self {
+        Self {
+            coefficients: vec![n],
+        }
+    }
+}
+
+impl Default for ContinuedFraction {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl fmt::Display for ContinuedFraction {
+    /// Standard notation `[a0; a1, a2, ...]`, the empty fraction displays as `[0]`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((a0, rest)) = self.coefficients.split_first() else {
+            return write!(f, "[0]");
+        };
+
+        write!(f, "[{}", a0)?;
+
+        for (i, a) in rest.iter().enumerate() {
+            let sep = if i == 0 { "; " } else { ", " };
+            write!(f, "{}{}", sep, a)?;
+        }
+
+        write!(f, "]")
+    }
+}
+
+impl fmt::Debug for ContinuedFraction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ContinuedFraction{}", self)
+    }
+}
+
+impl PartialEq for ContinuedFraction {
+    fn eq(&self, other: &Self) -> bool {
+        self.normalize().coefficients == other.normalize().coefficients
+    }
+}
+
+impl Eq for ContinuedFraction {}
+
+impl Hash for ContinuedFraction {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hash the canonical form so equal fractions hash identically
+        self.normalize().coefficients.hash(state);
+    }
+}
+
+impl PartialOrd for ContinuedFraction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ContinuedFraction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_coefficients(
+            &self.normalize().coefficients,
+            &other.normalize().coefficients,
+        )
+    }
+}
+
+/// Compare two canonical coefficient sequences by value.
+fn cmp_coefficients(a: &[i32], b: &[i32]) -> Ordering {
+    // A larger coefficient increases the value at even depths and decreases it at odd ones
+    let flip = |i: usize, ord: Ordering| {
+        if i.is_multiple_of(2) {
+            ord
+        } else {
+            ord.reverse()
+        }
+    };
+
+    for (i, (x, y)) in a.iter().zip(b).enumerate() {
+        if x != y {
+            return flip(i, x.cmp(y));
+        }
+    }
+
+    // When one sequence is a prefix of the other, its missing coefficient acts as +infinity
+    match a.len().cmp(&b.len()) {
+        Ordering::Less => flip(a.len(), Ordering::Greater),
+        Ordering::Greater => flip(b.len(), Ordering::Less),
+        Ordering::Equal => Ordering::Equal,
+    }
+}
+
+impl Neg for ContinuedFraction {
+    type Output = Self;
+
+    /// Uses `-[a0; a1, a2, ...] = [-a0 - 1; 1, a1 - 1, a2, ...]`, folding away `a1 - 1 == 0`.
+    fn neg(self) -> Self {
+        let coefficients = match self.normalize().coefficients.as_slice() {
+            [a0] => vec![-a0],
+            [a0, 1, a2, rest @ ..] => [-a0 - 1, a2 + 1].iter().chain(rest).copied().collect(),
+            [a0, a1, rest @ ..] => [-a0 - 1, 1, a1 - 1].iter().chain(rest).copied().collect(),
+            [] => unreachable!("canonical form is never empty"),
+        };
+
+        Self { coefficients }.normalize()
+    }
+}
+
+impl Add for ContinuedFraction {
+    type Output = Self;
+
+    /// Gosper's algorithm with `z = (x + y) / 1`.
+    fn add(self, other: Self) -> Self {
+        bihomographic(
+            &self.normalize().coefficients,
+            &other.normalize().coefficients,
+            [0, 1, 1, 0, 1, 0, 0, 0],
+        )
+    }
+}
+
+impl Mul for ContinuedFraction {
+    type Output = Self;
+
+    /// Gosper's algorithm with `z = x*y / 1`, or the unary `z = k*x / 1` when either side
+    /// is an integer `k`.
+    fn mul(self, other: Self) -> Self {
+        let (x, y) = (
+            self.normalize().coefficients,
+            other.normalize().coefficients,
+        );
+
+        match (x.as_slice(), y.as_slice()) {
+            (&[k], _) => other.scale(k),
+            (_, &[k]) => self.scale(k),
+            _ => bihomographic(&x, &y, [0, 0, 0, 1, 1, 0, 0, 0]),
+        }
+    }
+}
+
+/// Euclidean gcd for non-negative 128-bit intermediates.
+fn gcd_i128(mut n: i128, mut m: i128) -> i128 {
+    while m != 0 {
+        (n, m) = (m, n % m);
+    }
+
+    n
+}
+
+/// Drop trailing zero coefficients, the zero polynomial becomes empty.
+fn poly_trim(poly: &mut Vec<i128>) {
+    while poly.last() == Some(&0) {
+        poly.pop();
+    }
+}
+
+/// `c*a - k*x^shift*b` for polynomials in increasing powers, `None` on overflow.
+fn poly_scaled_sub(a: &[i128], c: i128, b: &[i128], k: i128, shift: usize) -> Option<Vec<i128>> {
+    let len = a.len().max(b.len() + shift);
+    let mut out = Vec::with_capacity(len);
+
+    for i in 0..len {
+        let x = a.get(i).copied().unwrap_or(0).checked_mul(c)?;
+        let y = match i.checked_sub(shift).and_then(|j| b.get(j)) {
+            Some(&b) => b.checked_mul(k)?,
+            None => 0,
+        };
+        out.push(x.checked_sub(y)?);
+    }
+
+    poly_trim(&mut out);
+    Some(out)
+}
+
+/// One fraction-free step of the extended Euclidean algorithm on polynomials: pseudo-divide
+/// `r0` by `r1` and return the remainder with its Bézout cofactor, reduced by their common
+/// content.
+fn poly_euclid_step(
+    r0: &[i128],
+    t0: &[i128],
+    r1: &[i128],
+    t1: &[i128],
+) -> Option<(Vec<i128>, Vec<i128>)> {
+    let lead = *r1.last()?;
+    let (mut r, mut t) = (r0.to_vec(), t0.to_vec());
+
+    // Each round cancels the leading term: r <- lead*r - top*x^shift*r1, likewise for t
+    while r.len() >= r1.len() {
+        let top = *r.last()?;
+        let shift = r.len() - r1.len();
+
+        r = poly_scaled_sub(&r, lead, r1, top, shift)?;
+        t = poly_scaled_sub(&t, lead, t1, top, shift)?;
+    }
+
+    let content = r.iter().chain(&t).fold(0, |g, &c| gcd_i128(g, c.abs()));
+    if content > 1 {
+        r.iter_mut().chain(&mut t).for_each(|c| *c /= content);
+    }
+
+    Some((r, t))
+}
+
+/// Smallest `x >= 0` with `l <= a*x mod m <= r`, for `0 <= l <= r < m` and `0 <= a < m`.
+///
+/// Either `a*ceil(l/a)` already lands in the window, or the problem reduces to the one
+/// over the multiples of `-m mod a` modulo `a`, the same step as Euclid's algorithm.
+/// Reflecting `a > m/2` to `m - a` first keeps the recursion depth logarithmic.
+#[cfg(feature = "std")]
+fn first_multiple_in(a: i128, m: i128, l: i128, r: i128) -> Option<i128> {
+    if l == 0 {
+        return Some(0);
+    }
+    if a == 0 {
+        return None;
+    }
+    if 2 * a > m {
+        // a*x ≡ -(m - a)*x, so the window flips to [m - r, m - l], which excludes 0
+        return first_multiple_in(m - a, m, m - r, m - l);
+    }
+
+    let x = (l + a - 1) / a;
+    if a * x <= r {
+        return Some(x);
+    }
+
+    // a*x = l' + m*y for some l' in [l, r], the window is now narrower than a
+    let y = first_multiple_in((a - m % a) % a, a, l % a, r % a)?;
+    Some((l + m * y + a - 1) / a)
+}
+
+/// `floor(n / d)` for `d != 0`.
+fn floor_div(n: i128, d: i128) -> i128 {
+    let q = n / d;
+
+    if n % d != 0 && (n < 0) != (d < 0) {
+        q - 1
+    } else {
+        q
+    }
+}
+
+/// Gosper's homographic algorithm, streams the coefficients of `z = (a*x + b) / (c*x + d)`
+/// given those of `x`.
+fn homographic(x: &[i32], state: [i128; 4]) -> ContinuedFraction {
+    let [mut a, mut b, mut c, mut d] = state;
+
+    let mut coefficients = Vec::new();
+    let mut xs = x.iter();
+    let mut started = false;
+
+    loop {
+        // With x in [0, inf] z lies between b/d and a/c
+        if started && c != 0 && d != 0 && (c < 0) == (d < 0) {
+            let r = floor_div(a, c);
+
+            if r == floor_div(b, d) {
+                coefficients.push(i32::try_from(r).expect("coefficient overflowed i32"));
+                (a, b, c, d) = (c, d, a - r * c, b - r * d);
+                continue;
+            }
+        }
+
+        started = true;
+
+        match xs.next() {
+            // x = p + 1/x'
+            Some(&p) => {
+                let p = p as i128;
+                (a, b, c, d) = (a * p + b, a, c * p + d, c);
+            }
+            // x = inf, z is the constant a/c
+            None => break,
+        }
+    }
+
+    while c != 0 {
+        let r = floor_div(a, c);
+        coefficients.push(i32::try_from(r).expect("coefficient overflowed i32"));
+        (a, c) = (c, a - r * c);
+    }
+
+    // No coefficient at all means z = a/0
+    assert!(!coefficients.is_empty(), "division by zero");
+
+    ContinuedFraction { coefficients }.normalize()
+}
+
+/// Gosper's bihomographic algorithm, streams the coefficients of
+/// `z = (a + b*x + c*y + d*x*y) / (e + f*x + g*y + h*x*y)` given those of `x` and `y`.
+fn bihomographic(x: &[i32], y: &[i32], state: [i128; 8]) -> ContinuedFraction {
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+
+    let mut coefficients = Vec::new();
+    let (mut xs, mut ys) = (x.iter(), y.iter());
+    let (mut x_started, mut y_started) = (false, false);
+    let (mut x_done, mut y_done) = (false, false);
+
+    while !(x_done && y_done) {
+        // With x, y in [0, inf] z lies between the four corner ratios a/e, b/f, c/g and d/h,
+        // so once they share an integer part it can be emitted
+        if x_started && y_started {
+            let same_sign = [f, g, h]
+                .iter()
+                .all(|&den| den != 0 && (den < 0) == (e < 0));
+
+            if e != 0 && same_sign {
+                let r = floor_div(a, e);
+
+                if r == floor_div(b, f) && r == floor_div(c, g) && r == floor_div(d, h) {
+                    coefficients.push(i32::try_from(r).expect("coefficient overflowed i32"));
+                    (a, b, c, d, e, f, g, h) =
+                        (e, f, g, h, a - r * e, b - r * f, c - r * g, d - r * h);
+                    continue;
+                }
+            }
+        }
+
+        // Pull a term from whichever input currently leaves z less determined
+        let ingest_x = if x_done || y_done || !x_started || !y_started {
+            !x_done && (y_done || !x_started)
+        } else {
+            let spread = |num: i128, den: i128| {
+                if e == 0 || den == 0 {
+                    f64::INFINITY
+                } else {
+                    (num as f64 / den as f64 - a as f64 / e as f64).abs()
+                }
+            };
+
+            spread(b, f) >= spread(c, g)
+        };
+
+        if ingest_x {
+            x_started = true;
+
+            match xs.next() {
+                // x = p + 1/x'
+                Some(&p) => {
+                    let p = p as i128;
+                    (a, b, c, d) = (b, a + b * p, d, c + d * p);
+                    (e, f, g, h) = (f, e + f * p, h, g + h * p);
+                }
+                // x = inf, z no longer depends on x
+                None => {
+                    x_done = true;
+                    (a, c, e, g) = (b, d, f, h);
+                }
+            }
+        } else {
+            y_started = true;
+
+            match ys.next() {
+                // y = q + 1/y'
+                Some(&q) => {
+                    let q = q as i128;
+                    (a, b, c, d) = (c, d, a + c * q, b + d * q);
+                    (e, f, g, h) = (g, h, e + g * q, f + h * q);
+                }
+                // y = inf, z no longer depends on y
+                None => {
+                    y_done = true;
+                    (a, b, e, f) = (c, d, g, h);
+                }
+            }
+        }
+    }
+
+    // z is now the constant a/e, finish with the Euclidean algorithm
+    while e != 0 {
+        let r = floor_div(a, e);
+        coefficients.push(i32::try_from(r).expect("coefficient overflowed i32"));
+        (a, e) = (e, a - r * e);
+    }
+
+    ContinuedFraction { coefficients }.normalize()
+}
+
+/// Error returned by fallible [`ContinuedFraction`] constructors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContinuedFractionError {
+    /// The input cannot be represented, e.g. it involves `i32::MIN`.
+    InvalidInput(String),
+    /// The denominator is zero.
+    DivisionByZero,
+}
+
+impl fmt::Display for ContinuedFractionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidInput(message) => write!(f, "invalid input: {}", message),
+            Self::DivisionByZero => write!(f, "division by zero"),
+        }
+    }
+}
+
+impl Error for ContinuedFractionError {}
+
+/// Error returned by [`ContinuedFraction::from_coefficients`] for an invalid sequence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidCoefficients {
+    /// There are no coefficients at all.
+    Empty,
+    /// A coefficient after `a0` is zero or negative.
+    NonPositive { index: usize, value: i32 },
+}
+
+impl fmt::Display for InvalidCoefficients {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "no coefficients"),
+            Self::NonPositive { index, value } => {
+                write!(f, "coefficient a{} = {} is not positive", index, value)
+            }
+        }
+    }
+}
+
+impl Error for InvalidCoefficients {}
+
+/// Checks shared by [`ContinuedFraction::from_coefficients`] and `TryFrom<Vec<i32>>`.
+fn validate_coefficients(coefficients: &[i32]) -> Result<(), InvalidCoefficients> {
+    if coefficients.is_empty() {
+        return Err(InvalidCoefficients::Empty);
+    }
+
+    if let Some((index, &value)) = coefficients
+        .iter()
+        .enumerate()
+        .skip(1)
+        .find(|&(_, &a)| a <= 0)
+    {
+        return Err(InvalidCoefficients::NonPositive { index, value });
+    }
+
+    Ok(())
+}
+
+/// Whether `coefficients` is a valid finite continued fraction: non-empty, with every
+/// coefficient after the first positive. Same check as [`ContinuedFraction::from_coefficients`].
+pub fn is_valid_cf(coefficients: &[i32]) -> bool {
+    validate_coefficients(coefficients).is_ok()
+}
+
+/// In-place version of [`ContinuedFraction::normalize`]: an empty sequence becomes `[0]` and a
+/// trailing 1 is folded into the previous coefficient.
+pub fn canonicalize(coefficients: &mut Vec<i32>) {
+    match coefficients.as_slice() {
+        [] => coefficients.push(0),
+        [.., _, 1] => {
+            coefficients.pop();
+            *coefficients.last_mut().unwrap() += 1;
+        }
+        _ => {}
+    }
+}
+
+/// Error returned by [`ContinuedFraction::rationalize`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RationalizeError {
+    /// The value or the tolerance is NaN or infinite.
+    NotFinite,
+    /// The tolerance is negative.
+    NegativeEpsilon,
+    /// The simplest fraction within the tolerance does not fit in an `i32`.
+    Overflow,
+}
+
+impl fmt::Display for RationalizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFinite => write!(f, "cannot rationalize a non-finite value"),
+            Self::NegativeEpsilon => write!(f, "tolerance must not be negative"),
+            Self::Overflow => write!(f, "rational approximation overflows i32"),
+        }
+    }
+}
+
+impl Error for RationalizeError {}
+
+/// Incremental constructor returned by [`ContinuedFraction::with_capacity`].
+#[derive(Debug, Clone, Default)]
+pub struct ContinuedFractionBuilder {
+    coefficients: Vec<i32>,
+}
+
+impl ContinuedFractionBuilder {
+    /// Append the next coefficient.
+    pub fn push(&mut self, a: i32) -> &mut Self {
+        self.coefficients.push(a);
+        self
+    }
+
+    /// Validate like [`ContinuedFraction::from_coefficients`] and hand over the buffer.
+    pub fn build(self) -> Result<ContinuedFraction, InvalidCoefficients> {
+        ContinuedFraction::try_from(self.coefficients)
+    }
+}
+
+/// Error returned when parsing a [`ContinuedFraction`] from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseContinuedFractionError {
+    message: String,
+}
+
+impl ParseContinuedFractionError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseContinuedFractionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid continued fraction: {}", self.message)
+    }
+}
+
+impl Error for ParseContinuedFractionError {}
+
+impl FromStr for ContinuedFraction {
+    type Err = ParseContinuedFractionError;
+
+    /// Parse the standard notation `[a0; a1, a2, ...]` produced by `Display`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inner = s
+            .trim()
+            .strip_prefix('[')
+            .and_then(|s| s.strip_suffix(']'))
+            .ok_or_else(|| ParseContinuedFractionError::new("expected `[a0; a1, a2, ...]`"))?
+            .trim();
+
+        if inner.is_empty() {
+            return Err(ParseContinuedFractionError::new("empty brackets"));
+        }
+
+        let (head, tail) = match inner.split_once(';') {
+            Some((head, tail)) => (head, Some(tail)),
+            None => (inner, None),
+        };
+
+        let parse = |token: &str| {
+            let token = token.trim();
+            token.parse::<i32>().map_err(|_| {
+                ParseContinuedFractionError::new(format!("invalid coefficient `{}`", token))
+            })
+        };
+
+        let mut coefficients = vec![parse(head)?];
+
+        if let Some(tail) = tail {
+            for token in tail.split(',') {
+                coefficients.push(parse(token)?);
+            }
+        }
+
+        Self::from_coefficients(&coefficients)
+            .map_err(|err| ParseContinuedFractionError::new(err.to_string()))
+    }
+}
+
+/// Borrowing iterator over the coefficients of a [`ContinuedFraction`].
+pub struct ContinuedFractionIter<'a> {
+    coefficients: &'a [i32],
+    front: usize,
+    back: usize,
+}
+
+impl<'a> Iterator for ContinuedFractionIter<'a> {
+    type Item = &'a i32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.front += 1;
+        Some(&self.coefficients[self.front - 1])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for ContinuedFractionIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        Some(&self.coefficients[self.back])
+    }
+}
+
+impl ExactSizeIterator for ContinuedFractionIter<'_> {}
+
+impl<'a> IntoIterator for &'a ContinuedFraction {
+    type Item = &'a i32;
+    type IntoIter = ContinuedFractionIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for ContinuedFraction {
+    type Item = i32;
+    type IntoIter = vec::IntoIter<i32>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.coefficients.into_iter()
+    }
+}
+
+/// Lazy iterator over the convergents `(h_n, k_n)` of a [`ContinuedFraction`].
+pub struct ConvergentsIter<'a> {
+    coefficients: core::slice::Iter<'a, i32>,
+    // h_{-2} = 0, h_{-1} = 1
+    // k_{-2} = 1, k_{-1} = 0
+    h_2: i32,
+    k_2: i32,
+    h_1: i32,
+    k_1: i32,
+}
+
+impl Iterator for ConvergentsIter<'_> {
+    type Item = (i32, i32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let &a = self.coefficients.next()?;
+
+        // h_n = a_n * h_{n-1} + h_{n-2}
+        // k_n = a_n * k_{n-1} + k_{n-2}
+        let h = a * self.h_1 + self.h_2;
+        let k = a * self.k_1 + self.k_2;
+
+        (self.h_2, self.k_2) = (self.h_1, self.k_1);
+        (self.h_1, self.k_1) = (h, k);
+
+        Some((h, k))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.coefficients.size_hint()
+    }
+}
+
+impl ExactSizeIterator for ConvergentsIter<'_> {}
+
+/// 64-bit variant of [`ContinuedFraction`] for rationals whose convergents overflow `i32`.
+///
+/// Only built by [`from_rational`](Self::from_rational), so the coefficients are always
+/// canonical and compare equal exactly when the values do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContinuedFraction64 {
+    coefficients: Vec<i64>,
+}
+
+impl ContinuedFraction64 {
+    /// Convert a rational number p/q to its continued fraction representation, panics if
+    /// `q == 0`.
+    ///
+    /// `i64::MIN` is accepted as long as the reduced fraction fits in `i64` with a positive
+    /// denominator, e.g. `i64::MIN/3`, but `i64::MIN/-1` and `1/i64::MIN` panic.
+    pub fn from_rational(p: i64, q: i64) -> Self {
+        assert!(
+            q != 0,
+            "denominator must be non-zero, got from_rational({}, 0)",
+            p
+        );
+
+        // Reduce and fix the sign in i128, where negating i64::MIN cannot overflow
+        let d = gcd_i128(p.unsigned_abs().into(), q.unsigned_abs().into());
+        let (p_wide, q_wide) = (i128::from(p) / d, i128::from(q) / d);
+        let (p_wide, q_wide) = if q_wide < 0 {
+            (-p_wide, -q_wide)
+        } else {
+            (p_wide, q_wide)
+        };
+
+        let (Ok(mut p), Ok(mut q)) = (i64::try_from(p_wide), i64::try_from(q_wide)) else {
+            panic!("{}/{} overflows i64 when normalizing the sign", p, q);
+        };
+
+        let mut coefficients = Vec::new();
+
+        // Floor division keeps every coefficient after a0 positive, even for negative p
+        while q != 0 {
+            coefficients.push(p.div_euclid(q));
+            (p, q) = (q, p.rem_euclid(q));
+        }
+
+        Self { coefficients }
+    }
+
+    /// Convert the continued fraction back to a rational number (p, q).
+    pub fn to_rational(&self) -> (i64, i64) {
+        if self.coefficients.is_empty() {
+            return (0, 1);
+        }
+
+        let mut num = *self.coefficients.last().unwrap();
+        let mut den = 1;
+
+        for &coef in self.coefficients.iter().rev().skip(1) {
+            // coef * num may leave i64 even when the sum fits, as for i64::MIN / 3
+            (num, den) = ((coef as i128 * num as i128 + den as i128) as i64, num);
+        }
+
+        (num, den)
+    }
+
+    /// Get the continued fraction coefficients [a0; a1, a2, ...]
+    pub fn coefficients(&self) -> &[i64] {
+        &self.coefficients
+    }
+
+    /// Compute convergents pairs (h_n, k_n) where h_n/k_n approaches the original value.
+    pub fn convergents(&self) -> Vec<(i64, i64)> {
+        let mut result = Vec::new();
+
+        let (mut h_2, mut k_2) = (0, 1); // h_{n-2}, k_{n-2}
+        let (mut h_1, mut k_1) = (1, 0); // h_{n-1}, k_{n-1}
+
+        for &a in &self.coefficients {
+            let h = a * h_1 + h_2;
+            let k = a * k_1 + k_2;
+
+            result.push((h, k));
+
+            (h_2, k_2) = (h_1, k_1);
+            (h_1, k_1) = (h, k);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::any;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(9, 3), 3);
+        assert_eq!(gcd(48, 18), 6);
+        assert_eq!(gcd(7, 7), 7);
+        assert_eq!(gcd(100, 25), 25);
+        assert_eq!(gcd(17, 13), 1);
+    }
+
+    #[test]
+    fn test_gcd_negative() {
+        // Signs are dropped, the C code used to return an uninitialized value here
+        for (n, m) in [(-48, 18), (48, -18), (-48, -18)] {
+            assert_eq!(gcd(n, m), 6);
+        }
+        assert_eq!(gcd(-7, -7), 7);
+        assert_eq!(gcd(-17, 13), 1);
+        assert_eq!(gcd(i32::MAX, -i32::MAX), i32::MAX);
+
+        // Euclid handles the full range quickly
+        assert_eq!(gcd(1_836_311_903, -1_134_903_170), 1);
+    }
+
+    #[test]
+    fn test_gcd_u32() {
+        assert_eq!(gcd_u32(9, 3), 3);
+        assert_eq!(gcd_u32(48, 18), 6);
+        assert_eq!(gcd_u32(7, 7), 7);
+        assert_eq!(gcd_u32(100, 25), 25);
+        assert_eq!(gcd_u32(17, 13), 1);
+        assert_eq!(gcd_u32(0, 5), 5);
+        assert_eq!(gcd_u32(0, 0), 0);
+        assert_eq!(gcd_u32(u32::MAX, u32::MAX), u32::MAX);
+        assert_eq!(gcd_u32(u32::MAX, 3), 3);
+        assert_eq!(gcd_u32(u32::MAX, 0), u32::MAX);
+        assert_eq!(gcd_u32(1 << 31, 1 << 20), 1 << 20);
+    }
+
+    #[test]
+    fn test_gcd_zero() {
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(gcd(5, 0), 5);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(gcd(0, 1), 1);
+        assert_eq!(gcd(0, -5), 5);
+        assert_eq!(gcd(-5, 0), 5);
+        assert_eq!(SafePair::new(0, -7).gcd(), 7);
+
+        assert_eq!(ContinuedFraction::from_rational(0, 7).coefficients(), &[0]);
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(21, 6), 42);
+        assert_eq!(lcm(7, 7), 7);
+        assert_eq!(lcm(-4, 6), 12);
+        assert_eq!(lcm(0, 5), 0);
+        assert_eq!(lcm(5, 0), 0);
+
+        assert_eq!(lcm_checked(65536, 65537), None);
+        assert_eq!(lcm_checked(i32::MIN, 2), None);
+        assert_eq!(lcm_checked(i32::MIN, 0), Some(0));
+        assert_eq!(lcm_checked(46340, 46341), Some(46340 * 46341));
+
+        // The exported C symbol takes i32::MIN too, the result is widened to long long
+        let c_lcm = |n, m| unsafe { bindings::lcm(&mut bindings::Pair { n, m }) };
+        assert_eq!(c_lcm(i32::MIN, 2), 1 << 31);
+        assert_eq!(c_lcm(i32::MIN, i32::MIN), 1 << 31);
+        assert_eq!(c_lcm(-3, i32::MIN), 3 << 31);
+        assert_eq!(c_lcm(i32::MAX, i32::MIN), (i32::MAX as i64) << 31);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        assert_eq!(extended_gcd(0, 0), (0, 1, 0));
+        assert_eq!(extended_gcd(0, 5).0, 5);
+        assert_eq!(extended_gcd(5, 0), (5, 1, 0));
+
+        let test_cases = [(9, 3), (48, 18), (17, 13), (-48, 18), (48, -18), (-7, -21)];
+
+        for (a, b) in test_cases {
+            let (g, s, t) = extended_gcd(a, b);
+
+            assert_eq!(g, gcd(a.abs(), b.abs()), "Wrong gcd for ({}, {})", a, b);
+            assert_eq!(
+                a * s + b * t,
+                g,
+                "Bézout identity failed for ({}, {})",
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn test_continued_fraction() {
+        let cf = ContinuedFraction::from_rational(3, 1);
+        assert_eq!(cf.coefficients(), &[3]);
+
+        let cf = ContinuedFraction::from_rational(1, 2);
+        assert_eq!(cf.coefficients(), &[0, 2]);
+
+        let cf = ContinuedFraction::from_rational(89, 55);
+        assert_eq!(cf.coefficients(), &[1, 1, 1, 1, 1, 1, 1, 1, 2]);
+
+        let test_cases = [(3, 7), (22, 7), (1, 3), (5, 1), (89, 55)];
+
+        for (p, q) in test_cases {
+            let cf = ContinuedFraction::from_rational(p, q);
+            let (p2, q2) = cf.to_rational();
+
+            assert_eq!(p * q2, p2 * q, "Roundtrip failed for {}/{}", p, q);
+        }
+
+        let cf = ContinuedFraction::from_rational(22, 7);
+        let conv = cf.convergents();
+        assert_eq!(conv, vec![(3, 1), (22, 7)]);
+    }
+
+    #[test]
+    fn test_continued_fraction64() {
+        // F_31 / F_30
+        let cf = ContinuedFraction64::from_rational(1_346_269, 832_040);
+        assert_eq!(cf.coefficients().len(), 29);
+        assert_eq!(cf.to_rational(), (1_346_269, 832_040));
+
+        // F_60 / F_59, convergents well beyond i32
+        let cf = ContinuedFraction64::from_rational(1_548_008_755_920, 956_722_026_041);
+        assert_eq!(cf.coefficients()[..57], [1; 57]);
+        assert_eq!(cf.coefficients()[57], 2);
+        assert_eq!(cf.coefficients().len(), 58);
+        assert_eq!(cf.to_rational(), (1_548_008_755_920, 956_722_026_041));
+        assert_eq!(
+            cf.convergents().last(),
+            Some(&(1_548_008_755_920, 956_722_026_041))
+        );
+
+        let cf = ContinuedFraction64::from_rational(22, -7);
+        let (p, q) = cf.to_rational();
+        assert_eq!(p * 7, -22 * q);
+        assert_eq!(cf, ContinuedFraction64::from_rational(-44, 14));
+        assert_eq!(cf.clone(), cf);
+
+        // i64::MIN works whenever the reduced fraction fits
+        let cf = ContinuedFraction64::from_rational(i64::MIN, 3);
+        assert_eq!(cf.to_rational(), (i64::MIN, 3));
+        assert_eq!(
+            ContinuedFraction64::from_rational(i64::MIN, 2).coefficients(),
+            &[i64::MIN / 2]
+        );
+        assert_eq!(
+            ContinuedFraction64::from_rational(i64::MIN, i64::MIN).coefficients(),
+            &[1]
+        );
+        assert_eq!(
+            ContinuedFraction64::from_rational(0, i64::MIN).coefficients(),
+            &[0]
+        );
+        assert_eq!(
+            ContinuedFraction64::from_rational(2, i64::MIN).to_rational(),
+            (-1, 1 << 62)
+        );
+        assert!(
+            std::panic::catch_unwind(|| ContinuedFraction64::from_rational(1, i64::MIN)).is_err()
+        );
+        assert!(
+            std::panic::catch_unwind(|| ContinuedFraction64::from_rational(i64::MIN, -1)).is_err()
+        );
+
+        assert_eq!(gcd64(i64::MIN, 6), 2);
+        assert_eq!(gcd64(i64::MIN, i64::MAX), 1);
+        assert!(std::panic::catch_unwind(|| gcd64(i64::MIN, 0)).is_err());
+
+        for p in [0, 3] {
+            let err =
+                std::panic::catch_unwind(|| ContinuedFraction64::from_rational(p, 0)).unwrap_err();
+            assert_eq!(
+                err.downcast_ref::<String>().map(String::as_str),
+                Some(format!("denominator must be non-zero, got from_rational({}, 0)", p).as_str())
+            );
+        }
+    }
+
+    #[test]
+    fn test_iter() {
+        let cf = ContinuedFraction::from_rational(89, 55);
+
+        let mut count = 0;
+        for &a in &cf {
+            assert!(a == 1 || a == 2);
+            count += 1;
+        }
+        assert_eq!(count, 9);
+
+        assert_eq!(cf.iter().len(), 9);
+        assert_eq!(
+            cf.iter().rev().copied().collect::<Vec<_>>(),
+            vec![2, 1, 1, 1, 1, 1, 1, 1, 1]
+        );
+
+        let mut iter = cf.iter();
+        assert_eq!(iter.next_back(), Some(&2));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.len(), 7);
+
+        let cf = ContinuedFraction::from_rational(22, 7);
+        assert_eq!(cf.into_iter().collect::<Vec<_>>(), vec![3, 7]);
+    }
+
+    #[test]
+    fn test_display() {
+        let cf = ContinuedFraction::from_rational(3, 1);
+        assert_eq!(cf.to_string(), "[3]");
+
+        let cf = ContinuedFraction::from_rational(22, 7);
+        assert_eq!(cf.to_string(), "[3; 7]");
+
+        let cf = ContinuedFraction::from_rational(89, 55);
+        assert_eq!(cf.to_string(), "[1; 1, 1, 1, 1, 1, 1, 1, 2]");
+        assert_eq!(
+            format!("{:?}", cf),
+            "ContinuedFraction[1; 1, 1, 1, 1, 1, 1, 1, 2]"
+        );
+
+        let cf = ContinuedFraction {
+            coefficients: vec![],
+        };
+        assert_eq!(cf.to_string(), "[0]");
+        assert_eq!(format!("{:?}", cf), "ContinuedFraction[0]");
+    }
+
+    #[test]
+    fn test_from_str() {
+        let cf: ContinuedFraction = "[1; 1, 1, 2]".parse().unwrap();
+        assert_eq!(cf.coefficients(), &[1, 1, 1, 2]);
+
+        let cf: ContinuedFraction = " [ 3 ;7,  15 , 1 ] ".parse().unwrap();
+        assert_eq!(cf.coefficients(), &[3, 7, 15, 1]);
+
+        let cf: ContinuedFraction = "[-2]".parse().unwrap();
+        assert_eq!(cf.coefficients(), &[-2]);
+
+        assert!("[]".parse::<ContinuedFraction>().is_err());
+        assert!("[ ]".parse::<ContinuedFraction>().is_err());
+        assert!("1; 2".parse::<ContinuedFraction>().is_err());
+        assert!("[1; ]".parse::<ContinuedFraction>().is_err());
+        assert!("[1; 2,]".parse::<ContinuedFraction>().is_err());
+        assert!("[1; x]".parse::<ContinuedFraction>().is_err());
+        assert!("[1; 2; 3]".parse::<ContinuedFraction>().is_err());
+        assert!("[1; 99999999999]".parse::<ContinuedFraction>().is_err());
+
+        let err = "[]".parse::<ContinuedFraction>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid continued fraction: empty brackets"
+        );
+
+        let test_cases = [(3, 1), (1, 2), (89, 55), (3, 7), (22, 7), (1, 3), (5, 1)];
+
+        for (p, q) in test_cases {
+            let cf = ContinuedFraction::from_rational(p, q);
+            let parsed: ContinuedFraction = cf.to_string().parse().unwrap();
+
+            assert_eq!(
+                parsed.coefficients(),
+                cf.coefficients(),
+                "Roundtrip failed for {}/{}",
+                p,
+                q
+            );
+        }
+    }
+
+    #[test]
+    fn test_eq() {
+        assert_eq!(
+            ContinuedFraction::from_rational(2, 1),
+            ContinuedFraction::from_rational(2, 1)
+        );
+        assert_eq!(
+            ContinuedFraction::from_rational(4, 2),
+            ContinuedFraction::from_rational(-6, -3)
+        );
+        assert_ne!(
+            ContinuedFraction::from_rational(22, 7),
+            ContinuedFraction::from_rational(7, 22)
+        );
+
+        // [2] == [1; 1]
+        let short = ContinuedFraction {
+            coefficients: vec![2],
+        };
+        let long = ContinuedFraction {
+            coefficients: vec![1, 1],
+        };
+        assert_eq!(short, long);
+
+        // [3; 7] == [3; 6, 1]
+        let short = ContinuedFraction::from_rational(22, 7);
+        let long = ContinuedFraction {
+            coefficients: vec![3, 6, 1],
+        };
+        assert_eq!(short, long);
+
+        // A lone 1 is already canonical
+        assert_eq!(ContinuedFraction::from_rational(1, 1).coefficients(), &[1]);
+        assert_ne!(
+            ContinuedFraction::from_rational(1, 1),
+            ContinuedFraction::from_rational(2, 1)
+        );
+
+        let empty = ContinuedFraction {
+            coefficients: vec![],
+        };
+        let zero = ContinuedFraction {
+            coefficients: vec![0],
+        };
+        assert_eq!(empty, zero);
+    }
+
+    #[test]
+    fn test_ord() {
+        let third = ContinuedFraction::from_rational(1, 3);
+        let half = ContinuedFraction::from_rational(1, 2);
+        assert!(third < half);
+        assert!(ContinuedFraction::from_rational(22, 7) > ContinuedFraction::from_rational(3, 1));
+        assert!(
+            ContinuedFraction::from_rational(355, 113) < ContinuedFraction::from_rational(22, 7)
+        );
+        assert!(ContinuedFraction::from_rational(-1, 2) < ContinuedFraction::from_rational(1, 3));
+        assert_eq!(
+            ContinuedFraction::from_rational(-1, 2).coefficients(),
+            &[-1, 2]
+        );
+
+        let long = ContinuedFraction {
+            coefficients: vec![3, 6, 1],
+        };
+        assert_eq!(
+            long.cmp(&ContinuedFraction::from_rational(22, 7)),
+            Ordering::Equal
+        );
+
+        let mut sorted = [
+            ContinuedFraction::from_rational(5, 1),
+            ContinuedFraction::from_rational(-3, 7),
+            ContinuedFraction::from_rational(89, 55),
+            ContinuedFraction::from_rational(1, 3),
+        ];
+        sorted.sort();
+        let rationals: Vec<_> = sorted.iter().map(|cf| cf.to_rational()).collect();
+        assert_eq!(rationals, vec![(-3, 7), (1, 3), (89, 55), (5, 1)]);
+
+        // Pseudo-random coprime pairs, ordering must agree with cross-multiplication
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = |bound: i32| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % (2 * bound as u64)) as i32 - bound
+        };
+
+        let mut pairs = Vec::new();
+        while pairs.len() < 200 {
+            let (p, q) = (next(1000), next(1000).abs());
+            if q != 0 && p != 0 && gcd(p.abs(), q) == 1 {
+                pairs.push((p, q));
+            }
+        }
+
+        for w in pairs.windows(2) {
+            let ((p1, q1), (p2, q2)) = (w[0], w[1]);
+            let a = ContinuedFraction::from_rational(p1, q1);
+            let b = ContinuedFraction::from_rational(p2, q2);
+
+            assert_eq!(
+                a.cmp(&b),
+                (p1 * q2).cmp(&(p2 * q1)),
+                "Ordering failed for {}/{} vs {}/{}",
+                p1,
+                q1,
+                p2,
+                q2
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_from_f64() {
+        let cf = ContinuedFraction::from_f64(0.0, 10);
+        assert_eq!(cf.coefficients(), &[0]);
+
+        let cf = ContinuedFraction::from_f64(5.0, 10);
+        assert_eq!(cf.coefficients(), &[5]);
+
+        let cf = ContinuedFraction::from_f64(0.5, 10);
+        assert_eq!(cf.coefficients(), &[0, 2]);
+
+        let cf = ContinuedFraction::from_f64(-0.5, 10);
+        assert_eq!(cf, ContinuedFraction::from_rational(-1, 2));
+
+        let cf = ContinuedFraction::from_f64(3.25, 10);
+        assert_eq!(cf.coefficients(), &[3, 4]);
+
+        let cf = ContinuedFraction::from_f64(std::f64::consts::PI, 5);
+        assert_eq!(cf.coefficients(), &[3, 7, 15, 1, 292]);
+
+        let cf = ContinuedFraction::from_f64(std::f64::consts::SQRT_2, 2);
+        assert_eq!(cf.coefficients(), &[1, 2]);
+
+        assert!(std::panic::catch_unwind(|| ContinuedFraction::from_f64(f64::NAN, 10)).is_err());
+        assert!(
+            std::panic::catch_unwind(|| ContinuedFraction::from_f64(f64::INFINITY, 10)).is_err()
+        );
+        assert!(std::panic::catch_unwind(|| ContinuedFraction::from_f64(0.5, 0)).is_err());
+    }
+
+    #[test]
+    fn test_to_f64() {
+        let cf = ContinuedFraction::from_rational(22, 7);
+        assert!((cf.to_f64() - 22.0 / 7.0).abs() < f64::EPSILON);
+
+        let cf = ContinuedFraction::from_rational(-3, 7);
+        assert!((cf.to_f64() + 3.0 / 7.0).abs() < f64::EPSILON);
+
+        let cf = ContinuedFraction::from_rational(5, 1);
+        assert_eq!(cf.to_f64(), 5.0);
+
+        let cf = ContinuedFraction::from_rational(103993, 33102);
+        assert!((cf.to_f64() - std::f64::consts::PI).abs() < 1e-9);
+
+        let cf = ContinuedFraction {
+            coefficients: vec![],
+        };
+        assert_eq!(cf.to_f64(), 0.0);
+    }
+
+    #[test]
+    fn test_to_rational_checked() {
+        let test_cases = [(3, 7), (22, 7), (-3, 7), (46368, 28657), (89, 55)];
+
+        for (p, q) in test_cases {
+            let cf = ContinuedFraction::from_rational(p, q);
+
+            assert_eq!(cf.to_rational_checked(), Some((p, q)));
+            assert_eq!(cf.to_rational_i64(), (p as i64, q as i64));
+        }
+
+        // [1; 1, 1, ...] with 50 ones is F_51 / F_50, beyond i32
+        let cf = ContinuedFraction {
+            coefficients: vec![1; 50],
+        };
+        assert_eq!(cf.to_rational_checked(), None);
+        assert_eq!(cf.to_rational_i64(), (20_365_011_074, 12_586_269_025));
+
+        let cf = ContinuedFraction {
+            coefficients: vec![],
+        };
+        assert_eq!(cf.to_rational_checked(), Some((0, 1)));
+        assert_eq!(cf.to_rational_i64(), (0, 1));
+    }
+
+    #[test]
+    fn test_convergents_checked() {
+        let cf = ContinuedFraction::from_rational(22, 7);
+        assert_eq!(cf.convergents_checked(), vec![Some((3, 1)), Some((22, 7))]);
+        assert_eq!(cf.convergents_i64(), vec![(3, 1), (22, 7)]);
+
+        // F_47 = 2_971_215_073 is the first Fibonacci number beyond i32::MAX
+        let cf = ContinuedFraction {
+            coefficients: vec![1; 50],
+        };
+        let checked = cf.convergents_checked();
+        let wide = cf.convergents_i64();
+
+        assert_eq!(checked.len(), 50);
+        assert_eq!(checked[44], Some((1_836_311_903, 1_134_903_170)));
+        assert_eq!(checked[45], None);
+        assert!(checked[45..].iter().all(Option::is_none));
+
+        assert_eq!(wide[44], (1_836_311_903, 1_134_903_170));
+        assert_eq!(wide[45], (2_971_215_073, 1_836_311_903));
+        assert_eq!(wide[49], (20_365_011_074, 12_586_269_025));
+
+        for (c, w) in checked.iter().zip(&wide).take(45) {
+            assert_eq!(c.map(|(h, k)| (h as i64, k as i64)), Some(*w));
+        }
+    }
+
+    #[test]
+    fn test_best_approximation() {
+        let pi = ContinuedFraction {
+            coefficients: vec![3, 7, 15, 1, 292, 1, 1, 1, 2, 1, 3, 1],
+        };
+        assert_eq!(pi.best_approximation(1), (3, 1));
+        assert_eq!(pi.best_approximation(7), (22, 7));
+        assert_eq!(pi.best_approximation(56), (22, 7));
+        assert_eq!(pi.best_approximation(57), (179, 57));
+        assert_eq!(pi.best_approximation(100), (311, 99));
+        assert_eq!(pi.best_approximation(1000), (355, 113));
+
+        let cf = ContinuedFraction::from_rational(22, 7);
+        assert_eq!(cf.best_approximation(7), (22, 7));
+        assert_eq!(cf.best_approximation(1000), (22, 7));
+
+        // 7/2 is equally close to 3 and 4, the convergent wins
+        let cf = ContinuedFraction::from_rational(7, 2);
+        assert_eq!(cf.best_approximation(1), (3, 1));
+
+        // Brute force: no fraction with a small enough denominator is strictly closer
+        let test_cases = [
+            (3, 7),
+            (89, 55),
+            (-13, 17),
+            (1000, 333),
+            (355, 113),
+            (17, 12),
+        ];
+
+        for (p0, q0) in test_cases {
+            let cf = ContinuedFraction::from_rational(p0, q0);
+            let (p0, q0) = (p0 as i64, q0 as i64);
+
+            // |p/q - p0/q0| scaled by q0
+            let distance = |p: i64, q: i64| ((p * q0 - p0 * q).abs(), q);
+            let closer = |(n1, d1): (i64, i64), (n2, d2): (i64, i64)| n1 * d2 < n2 * d1;
+
+            for max_denom in 1..30 {
+                let (p, q) = cf.best_approximation(max_denom);
+                assert!(q <= max_denom);
+                assert_eq!(extended_gcd(p, q).0, 1);
+
+                for q2 in 1..=max_denom as i64 {
+                    let p2 = (p0 * q2).div_euclid(q0);
+
+                    for p2 in [p2, p2 + 1] {
+                        assert!(
+                            !closer(distance(p2, q2), distance(p as i64, q as i64)),
+                            "{}/{} is closer to {}/{} than {}/{}",
+                            p2,
+                            q2,
+                            p0,
+                            q0,
+                            p,
+                            q
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mediant() {
+        assert_eq!(mediant((1, 2), (2, 3)), (3, 5));
+        assert_eq!(mediant((0, 1), (1, 1)), (1, 2));
+        assert_eq!(mediant_checked((1, 2), (2, 3)), Some((3, 5)));
+        assert_eq!(mediant_checked((i32::MAX, 1), (1, 1)), None);
+        assert_eq!(mediant_checked((1, i32::MAX), (1, 1)), None);
+
+        // Convergents of 89/55 are consecutive Fibonacci ratios, except for the final 89/55
+        let cf = ContinuedFraction::from_rational(89, 55);
+        let mediants = cf.mediant_sequence();
+        assert_eq!(mediants.len(), 8);
+        assert_eq!(mediants[0], (3, 2));
+        assert_eq!(mediants[7], (123, 76));
+
+        let checked: Vec<_> = mediants.iter().copied().map(Some).collect();
+        assert_eq!(cf.mediant_sequence_checked(), checked);
+
+        let cf = ContinuedFraction::from_rational(22, 7);
+        assert_eq!(cf.mediant_sequence(), vec![(25, 8)]);
+
+        let cf = ContinuedFraction {
+            coefficients: vec![1; 50],
+        };
+        let checked = cf.mediant_sequence_checked();
+        assert_eq!(checked[42], Some((1_836_311_903, 1_134_903_170)));
+        assert_eq!(checked[43], None);
+        assert_eq!(checked[48], None);
+    }
+
+    #[test]
+    fn test_semi_convergents() {
+        let pi = ContinuedFraction {
+            coefficients: vec![3, 7, 15, 1, 292],
+        };
+        let semi = pi.semi_convergents();
+
+        assert_eq!(semi.len(), 1 + 7 + 15 + 1 + 292);
+        assert!(semi.contains(&(333, 106)));
+        assert!(semi.contains(&(311, 99)));
+        assert!(semi.contains(&(355, 113)));
+
+        // From the smallest, 3/1, up through 25/8 towards pi, and down from 4/1 above it
+        assert_eq!(semi[..2], [(3, 1), (25, 8)]);
+        assert_eq!(semi[semi.len() - 2..], [(7, 2), (4, 1)]);
+
+        for convergent in pi.convergents() {
+            assert!(semi.contains(&convergent));
+        }
+
+        for w in semi.windows(2) {
+            assert!(i64::from(w[0].0) * i64::from(w[1].1) < i64::from(w[1].0) * i64::from(w[0].1));
+        }
+
+        // k_2 = 1 + j * 100_000 overflows i32 after j = 21_474
+        let cf = ContinuedFraction::from_coefficients(&[0, 100_000, 100_000]).unwrap();
+        assert_eq!(cf.semi_convergents().len(), 1 + 100_000 + 21_474);
+
+        let cf = ContinuedFraction::from_rational(5, 1);
+        assert_eq!(cf.semi_convergents(), vec![(5, 1)]);
+    }
+
+    #[test]
+    fn test_tail() {
+        let cf = ContinuedFraction::from_rational(89, 55);
+        assert_eq!(cf.tail(0).to_rational(), (89, 55));
+        assert_eq!(cf.tail(1).to_rational(), (55, 34));
+        assert_eq!(cf.tail(2).to_rational(), (34, 21));
+        assert_eq!(cf.tail(8).to_rational(), (2, 1));
+
+        let cf = ContinuedFraction::from_rational(355, 113);
+        assert_eq!(cf.tail(1).coefficients(), &[7, 16]);
+
+        assert!(std::panic::catch_unwind(|| cf.tail(3)).is_err());
+    }
+
+    #[test]
+    fn test_truncate() {
+        let test_cases = [(89, 55), (355, 113), (-3, 7), (5, 1)];
+
+        for (p, q) in test_cases {
+            let cf = ContinuedFraction::from_rational(p, q);
+            let convergents = cf.convergents();
+
+            for k in 1..=convergents.len() {
+                assert_eq!(cf.truncate(k).to_rational(), convergents[k - 1]);
+            }
+
+            assert_eq!(cf.truncate(convergents.len() + 5), cf);
+        }
+
+        let cf = ContinuedFraction::from_rational(355, 113);
+        assert_eq!(cf.truncate(2).to_rational(), (22, 7));
+        assert!(std::panic::catch_unwind(|| cf.truncate(0)).is_err());
+    }
+
+    #[test]
+    fn test_neg() {
+        let test_cases = [
+            (3, 7),
+            (22, 7),
+            (-3, 7),
+            (1, 2),
+            (-1, 2),
+            (5, 1),
+            (-5, 1),
+            (89, 55),
+        ];
+
+        for (p, q) in test_cases {
+            let cf = ContinuedFraction::from_rational(p, q);
+            let neg = -ContinuedFraction::from_rational(p, q);
+
+            assert_eq!(
+                neg.to_rational(),
+                (-p, q),
+                "Negation failed for {}/{}",
+                p,
+                q
+            );
+            assert_eq!(neg, ContinuedFraction::from_rational(-p, q));
+            assert_eq!(-neg, cf);
+        }
+
+        assert_eq!(
+            (-ContinuedFraction::from_rational(3, 7)).coefficients(),
+            &[-1, 1, 1, 3]
+        );
+        assert_eq!(
+            (-ContinuedFraction::from_rational(1, 3)).coefficients(),
+            &[-1, 1, 2]
+        );
+        assert_eq!(
+            (-ContinuedFraction::from_rational(1, 2)).coefficients(),
+            &[-1, 2]
+        );
+    }
+
+    #[test]
+    fn test_add() {
+        let test_cases = [
+            ((1, 2), (1, 3)),
+            ((22, 7), (355, 113)),
+            ((-3, 7), (1, 2)),
+            ((89, 55), (-34, 21)),
+            ((5, 1), (2, 1)),
+            ((-1, 3), (-1, 6)),
+            ((13, 4), (-13, 1)),
+        ];
+
+        for ((p1, q1), (p2, q2)) in test_cases {
+            let sum =
+                ContinuedFraction::from_rational(p1, q1) + ContinuedFraction::from_rational(p2, q2);
+            let expected = ContinuedFraction::from_rational(p1 * q2 + p2 * q1, q1 * q2);
+
+            assert_eq!(sum, expected, "{}/{} + {}/{}", p1, q1, p2, q2);
+            assert_eq!(sum.coefficients(), expected.coefficients());
+        }
+    }
+
+    #[test]
+    fn test_mul() {
+        let test_cases = [
+            ((1, 2), (1, 3)),
+            ((22, 7), (355, 113)),
+            ((-3, 7), (1, 2)),
+            ((89, 55), (55, 89)),
+            ((5, 1), (2, 1)),
+            ((-1, 3), (-1, 6)),
+            ((13, 4), (3, 1)),
+            ((-2, 1), (89, 55)),
+        ];
+
+        for ((p1, q1), (p2, q2)) in test_cases {
+            let product =
+                ContinuedFraction::from_rational(p1, q1) * ContinuedFraction::from_rational(p2, q2);
+            let expected = ContinuedFraction::from_rational(p1 * p2, q1 * q2);
+
+            assert_eq!(product, expected, "{}/{} * {}/{}", p1, q1, p2, q2);
+            assert_eq!(product.coefficients(), expected.coefficients());
+        }
+
+        let zero = ContinuedFraction {
+            coefficients: vec![0],
+        };
+        assert_eq!(
+            zero * ContinuedFraction::from_rational(22, 7),
+            ContinuedFraction {
+                coefficients: vec![0]
+            }
+        );
+    }
+
+    #[test]
+    fn test_reciprocal() {
+        let test_cases = [
+            (3, 7),
+            (22, 7),
+            (-3, 7),
+            (1, 2),
+            (-1, 2),
+            (5, 1),
+            (-5, 1),
+            (89, 55),
+        ];
+
+        for (p, q) in test_cases {
+            let cf = ContinuedFraction::from_rational(p, q);
+            let reciprocal = cf.reciprocal();
+
+            let (p1, q1) = cf.to_rational();
+            let (p2, q2) = reciprocal.to_rational();
+            assert_eq!(p1 * p2, q1 * q2, "Reciprocal failed for {}/{}", p, q);
+
+            assert_eq!(reciprocal.reciprocal(), cf);
+        }
+
+        let cf = ContinuedFraction::from_rational(22, 7);
+        assert_eq!(cf.reciprocal().coefficients(), &[0, 3, 7]);
+        assert_eq!(cf.reciprocal().reciprocal().coefficients(), &[3, 7]);
+
+        let zero = ContinuedFraction {
+            coefficients: vec![0],
+        };
+        assert!(std::panic::catch_unwind(|| zero.reciprocal()).is_err());
+    }
+
+    #[test]
+    fn test_floor_fract() {
+        assert!(ContinuedFraction::from_rational(6, 3).is_integer());
+        assert!(!ContinuedFraction::from_rational(22, 7).is_integer());
+        assert!(
+            ContinuedFraction {
+                coefficients: vec![2, 1],
+            }
+            .is_integer()
+        );
+
+        let cf = ContinuedFraction {
+            coefficients: vec![2, 1],
+        };
+        assert_eq!(cf.floor(), 3);
+        assert_eq!(cf.fract().coefficients(), &[0]);
+
+        let cf = ContinuedFraction::from_rational(-3, 7);
+        assert_eq!(cf.floor(), -1);
+        assert_eq!(cf.fract().to_rational(), (4, 7));
+
+        let test_cases = [
+            (3, 7),
+            (22, 7),
+            (-3, 7),
+            (5, 1),
+            (-5, 1),
+            (89, 55),
+            (-355, 113),
+        ];
+
+        for (p, q) in test_cases {
+            let cf = ContinuedFraction::from_rational(p, q);
+            let sum = cf.floor() as f64 + cf.fract().to_f64();
+
+            assert!(
+                (sum - cf.to_f64()).abs() < 1e-12,
+                "Split failed for {}/{}",
+                p,
+                q
+            );
+            assert!((0.0..1.0).contains(&cf.fract().to_f64()));
+        }
+    }
+
+    #[test]
+    fn test_try_from_rational() {
+        let cf = ContinuedFraction::try_from_rational(3, -7).unwrap();
+        assert_eq!(cf.to_rational(), (-3, 7));
+        assert_eq!(cf, ContinuedFraction::from_rational(-3, 7));
+
+        let cf = ContinuedFraction::try_from_rational(i32::MAX, -1).unwrap();
+        assert_eq!(cf.coefficients(), &[-i32::MAX]);
+
+        assert!(matches!(
+            ContinuedFraction::try_from_rational(i32::MIN, -1),
+            Err(ContinuedFractionError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            ContinuedFraction::try_from_rational(1, i32::MIN),
+            Err(ContinuedFractionError::InvalidInput(_))
+        ));
+        assert!(
+            std::panic::catch_unwind(|| ContinuedFraction::from_rational(i32::MIN, -1)).is_err()
+        );
+    }
+
+    #[test]
+    fn test_clone_default() {
+        let cf = ContinuedFraction::from_rational(22, 7);
+        let copy = cf.clone();
+        assert_eq!(copy.coefficients(), cf.coefficients());
+        assert_eq!(cf + copy, ContinuedFraction::from_rational(44, 7));
+
+        assert_eq!(ContinuedFraction::default().coefficients(), &[0]);
+        assert_eq!(ContinuedFraction::zero(), ContinuedFraction::default());
+        assert_eq!(ContinuedFraction::one().to_rational(), (1, 1));
+        assert_eq!(
+            ContinuedFraction::zero() + ContinuedFraction::one(),
+            ContinuedFraction::one()
+        );
+
+        let fractions = vec![ContinuedFraction::one(); 3];
+        assert_eq!(fractions.clone().len(), 3);
+    }
+
+    #[test]
+    fn test_from_tuple() {
+        assert_eq!(ContinuedFraction::from((22, 7)).coefficients(), &[3, 7]);
+        assert_eq!(
+            ContinuedFraction::from((3, -7)),
+            ContinuedFraction::from_rational(-3, 7)
+        );
+
+        let cf: ContinuedFraction = (355, 113).into();
+        assert_eq!(cf.to_rational(), (355, 113));
+
+        assert_eq!(
+            ContinuedFraction::try_from_rational(5, 0),
+            Err(ContinuedFractionError::DivisionByZero)
+        );
+        assert_eq!(
+            ContinuedFraction::try_from_rational(0, 0),
+            Err(ContinuedFractionError::DivisionByZero)
+        );
+        assert!(std::panic::catch_unwind(|| ContinuedFraction::from((1, 0))).is_err());
+    }
+
+    #[test]
+    fn test_from_i32() {
+        assert_eq!(ContinuedFraction::from(5i32).to_rational(), (5, 1));
+        assert_eq!(ContinuedFraction::from(0i32).to_rational(), (0, 1));
+        assert_eq!(ContinuedFraction::from(-4i32).to_rational(), (-4, 1));
+        assert_eq!(
+            ContinuedFraction::from(i32::MIN).coefficients(),
+            &[i32::MIN]
+        );
+        assert!(ContinuedFraction::from(7i32).is_integer());
+        assert_eq!(
+            ContinuedFraction::from(3i32),
+            ContinuedFraction::from_rational(6, 2)
+        );
+    }
+
+    #[test]
+    fn test_hash() {
+        use std::collections::HashMap;
+
+        let mut names = HashMap::new();
+        names.insert(
+            ContinuedFraction {
+                coefficients: vec![3, 7],
+            },
+            "22/7",
+        );
+        names.insert(ContinuedFraction::from_rational(1, 2), "1/2");
+
+        let key = ContinuedFraction {
+            coefficients: vec![3, 6, 1],
+        };
+        assert_eq!(names.get(&key), Some(&"22/7"));
+
+        let key = ContinuedFraction {
+            coefficients: vec![0, 1, 1],
+        };
+        assert_eq!(names.get(&key), Some(&"1/2"));
+
+        let empty = ContinuedFraction {
+            coefficients: vec![],
+        };
+        names.insert(empty, "zero");
+        assert_eq!(names.get(&ContinuedFraction::zero()), Some(&"zero"));
+        assert_eq!(names.len(), 3);
+    }
+
+    #[test]
+    fn test_approximation_error() {
+        let cf = ContinuedFraction::from_rational(103993, 33102);
+        let n = cf.coefficients().len();
+
+        let mut previous = f64::INFINITY;
+        for i in 0..n - 2 {
+            let (error, bound) = cf.approximation_error(i);
+            assert!(
+                error < bound,
+                "Bound failed at {}: {} >= {}",
+                i,
+                error,
+                bound
+            );
+            assert!(error < previous, "Error not decreasing at {}", i);
+            previous = error;
+        }
+        let (error, bound) = cf.approximation_error(n - 2);
+        assert!(error < previous);
+        assert!((error - bound).abs() < 1e-18);
+        assert_eq!(cf.approximation_error(n - 1), (0.0, 0.0));
+
+        let (error, bound) = cf.approximation_error(1);
+        assert!((error - (22.0 / 7.0 - 103993.0 / 33102.0)).abs() < 1e-15);
+        assert!((bound - 1.0 / (7.0 * 106.0)).abs() < 1e-15);
+
+        let cf = ContinuedFraction::from_rational(-89, 55);
+        for i in 0..cf.coefficients().len() {
+            let (error, bound) = cf.approximation_error(i);
+            assert!(error <= bound);
+        }
+    }
+
+    #[test]
+    fn test_farey_sequence() {
+        assert_eq!(farey_sequence(0), vec![]);
+        assert_eq!(farey_sequence(1), vec![(0, 1), (1, 1)]);
+        assert_eq!(
+            farey_sequence(5),
+            vec![
+                (0, 1),
+                (1, 5),
+                (1, 4),
+                (1, 3),
+                (2, 5),
+                (1, 2),
+                (3, 5),
+                (2, 3),
+                (3, 4),
+                (4, 5),
+                (1, 1)
+            ]
+        );
+
+        let sequence = farey_sequence(12);
+        for w in sequence.windows(2) {
+            let ((p, q), (r, s)) = (w[0], w[1]);
+            assert_eq!(
+                (p * s - q * r).abs(),
+                1,
+                "Neighbours {}/{} and {}/{}",
+                p,
+                q,
+                r,
+                s
+            );
+            assert!(p * s < q * r);
+            assert!(q <= 12 && s <= 12);
+        }
+    }
+
+    #[test]
+    fn test_stern_brocot_path() {
+        use Direction::{Left, Right};
+
+        assert_eq!(ContinuedFraction::one().stern_brocot_path(), vec![]);
+        assert_eq!(
+            ContinuedFraction::from_rational(1, 2).stern_brocot_path(),
+            vec![Left]
+        );
+        assert_eq!(
+            ContinuedFraction::from_rational(3, 1).stern_brocot_path(),
+            vec![Right, Right]
+        );
+        assert_eq!(
+            ContinuedFraction::from_rational(3, 5).stern_brocot_path(),
+            vec![Left, Right, Left]
+        );
+        assert_eq!(
+            ContinuedFraction::from_rational(22, 7).stern_brocot_path(),
+            vec![Right, Right, Right, Left, Left, Left, Left, Left, Left]
+        );
+
+        assert_eq!(
+            ContinuedFraction::from_stern_brocot_path(&[]).to_rational(),
+            (1, 1)
+        );
+        assert_eq!(
+            ContinuedFraction::from_stern_brocot_path(&[Left, Right, Left]).to_rational(),
+            (3, 5)
+        );
+
+        let test_cases = [
+            (1, 7),
+            (7, 1),
+            (22, 7),
+            (89, 55),
+            (355, 113),
+            (2, 3),
+            (13, 4),
+        ];
+
+        for (p, q) in test_cases {
+            let cf = ContinuedFraction::from_rational(p, q);
+            let path = cf.stern_brocot_path();
+            assert_eq!(ContinuedFraction::from_stern_brocot_path(&path), cf);
+            assert_eq!(
+                ContinuedFraction::from_stern_brocot_path(&path).stern_brocot_path(),
+                path
+            );
+        }
+
+        assert!(
+            std::panic::catch_unwind(|| ContinuedFraction::zero().stern_brocot_path()).is_err()
+        );
+        assert!(
+            std::panic::catch_unwind(|| ContinuedFraction::from_rational(-1, 2).stern_brocot_path())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_egyptian_fraction() {
+        assert_eq!(
+            ContinuedFraction::zero().egyptian_fraction(),
+            Vec::<i32>::new()
+        );
+        assert_eq!(
+            ContinuedFraction::from_rational(1, 3).egyptian_fraction(),
+            vec![3]
+        );
+        assert_eq!(
+            ContinuedFraction::from_rational(2, 3).egyptian_fraction(),
+            vec![2, 6]
+        );
+        assert_eq!(
+            ContinuedFraction::from_rational(4, 13).egyptian_fraction(),
+            vec![4, 18, 468]
+        );
+
+        let test_cases = [(3, 7), (5, 6), (7, 15), (6, 7), (21, 23), (1, 2)];
+
+        for (p, q) in test_cases {
+            let denominators = ContinuedFraction::from_rational(p, q).egyptian_fraction();
+
+            assert!(denominators.windows(2).all(|w| w[0] < w[1]));
+
+            // Sum the unit fractions exactly
+            let (mut num, mut den) = (0i128, 1i128);
+            for &n in &denominators {
+                (num, den) = (num * n as i128 + den, den * n as i128);
+                let d = gcd_i128(num, den);
+                (num, den) = (num / d, den / d);
+            }
+            assert_eq!(
+                (num, den),
+                (p as i128, q as i128),
+                "Sum failed for {}/{}",
+                p,
+                q
+            );
+        }
+
+        assert!(std::panic::catch_unwind(|| ContinuedFraction::one().egyptian_fraction()).is_err());
+        assert!(
+            std::panic::catch_unwind(|| ContinuedFraction::from_rational(-1, 2).egyptian_fraction())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 7), Some(5));
+        assert_eq!(mod_inverse(2, 4), None);
+        assert_eq!(mod_inverse(10, 17), Some(12));
+        assert_eq!(mod_inverse(-3, 7), Some(2));
+        assert_eq!(mod_inverse(1, 1), Some(0));
+        assert_eq!(mod_inverse(0, 5), None);
+        assert_eq!(mod_inverse(3, 0), None);
+        assert_eq!(mod_inverse(3, -7), None);
+        assert_eq!(mod_inverse(i32::MIN, 3), Some(1));
+
+        let m = 1_000_000_007;
+        for a in [2, 12345, m - 1, i32::MAX] {
+            let inv = mod_inverse(a, m).unwrap();
+            assert!((0..m).contains(&inv));
+            assert_eq!((a as i64 * inv as i64).rem_euclid(m as i64), 1);
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        let cf = ContinuedFraction::from_rational(3, 1);
+        assert_eq!(cf.coefficients(), &[3]);
+        assert!(cf.is_normalized());
+
+        let cf = ContinuedFraction {
+            coefficients: vec![2, 1],
+        };
+        assert!(!cf.is_normalized());
+        assert_eq!(cf.normalize().coefficients(), &[3]);
+        assert!(cf.normalize().is_normalized());
+
+        let cf = ContinuedFraction {
+            coefficients: vec![3, 6, 1],
+        };
+        assert_eq!(cf.normalize().coefficients(), &[3, 7]);
+
+        let cf = ContinuedFraction {
+            coefficients: vec![],
+        };
+        assert!(!cf.is_normalized());
+        assert_eq!(cf.normalize().coefficients(), &[0]);
+
+        assert!(ContinuedFraction::one().is_normalized());
+        assert!(ContinuedFraction::from_rational(89, 55).is_normalized());
+
+        let sum = ContinuedFraction {
+            coefficients: vec![0, 1, 1],
+        } + ContinuedFraction::from_rational(1, 2);
+        assert!(sum.is_normalized());
+        assert_eq!(sum.coefficients(), &[1]);
+    }
+
+    #[test]
+    fn test_from_coefficients() {
+        let cf = ContinuedFraction::from_coefficients(&[3, 7, 15, 1, 292]).unwrap();
+        assert_eq!(cf.to_rational(), (103993, 33102));
+
+        let cf = ContinuedFraction::from_coefficients(&[-2, 1, 3]).unwrap();
+        assert_eq!(cf.coefficients(), &[-2, 1, 3]);
+
+        let cf = ContinuedFraction::from_coefficients(&[2, 1]).unwrap();
+        assert!(!cf.is_normalized());
+        assert_eq!(cf, ContinuedFraction::from(3));
+
+        assert!(ContinuedFraction::from_coefficients(&[0]).is_ok());
+        assert_eq!(
+            ContinuedFraction::from_coefficients(&[]),
+            Err(InvalidCoefficients::Empty)
+        );
+        assert_eq!(
+            ContinuedFraction::from_coefficients(&[1, 2, 0, 3]),
+            Err(InvalidCoefficients::NonPositive { index: 2, value: 0 })
+        );
+        assert_eq!(
+            ContinuedFraction::from_coefficients(&[1, -4]),
+            Err(InvalidCoefficients::NonPositive {
+                index: 1,
+                value: -4
+            })
+        );
+
+        let err = "[1; 2, 0]".parse::<ContinuedFraction>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid continued fraction: coefficient a2 = 0 is not positive"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        let cf = ContinuedFraction::from_rational(103993, 33102);
+        let json = serde_json::to_string(&cf).unwrap();
+        assert_eq!(json, "[3,7,15,1,292]");
+
+        let parsed: ContinuedFraction = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.coefficients(), cf.coefficients());
+
+        let parsed: ContinuedFraction = serde_json::from_str("[2, 1]").unwrap();
+        assert_eq!(parsed.coefficients(), &[2, 1]);
+
+        assert!(serde_json::from_str::<ContinuedFraction>("[]").is_err());
+        assert!(serde_json::from_str::<ContinuedFraction>("[1, 0]").is_err());
+        assert!(serde_json::from_str::<ContinuedFraction>("{\"coefficients\": [1]}").is_err());
+
+        let err = serde_json::from_str::<ContinuedFraction>("[1, -2]").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("coefficient a1 = -2 is not positive")
+        );
+    }
+
+    #[test]
+    fn test_coprime() {
+        assert!(coprime(3, 7));
+        assert!(coprime(-3, 7));
+        assert!(coprime(8, -9));
+        assert!(!coprime(4, 6));
+        assert!(!coprime(-4, -6));
+        assert!(coprime(0, 1));
+        assert!(coprime(-1, 0));
+        assert!(!coprime(0, 0));
+        assert!(!coprime(0, 5));
+        assert!(coprime(i32::MIN, 3));
+        assert!(!coprime(i32::MIN, 2));
+
+        assert_eq!(
+            ContinuedFraction::from_rational(22, 7).coefficients(),
+            &[3, 7]
+        );
+        assert_eq!(
+            ContinuedFraction::from_rational(44, 14).coefficients(),
+            &[3, 7]
+        );
+    }
+
+    #[test]
+    fn test_gcd_slice() {
+        assert_eq!(gcd_slice(&[]), 0);
+        assert_eq!(gcd_slice(&[12]), 12);
+        assert_eq!(gcd_slice(&[-12]), 12);
+        assert_eq!(gcd_slice(&[12, 18]), 6);
+        assert_eq!(gcd_slice(&[12, 18, 8]), 2);
+        assert_eq!(gcd_slice(&[-12, 18, -30]), 6);
+        assert_eq!(gcd_slice(&[7, 13, 100]), 1);
+        assert_eq!(gcd_slice(&[0, 15, 25]), 5);
+        assert_eq!(gcd_slice(&[15, 0, 25, 0]), 5);
+        assert_eq!(gcd_slice(&[0, 0]), 0);
+        assert_eq!(gcd_slice(&[0]), 0);
+    }
+
+    #[test]
+    fn test_depth() {
+        assert_eq!(ContinuedFraction::from_rational(22, 7).depth(), 2);
+        assert_eq!(ContinuedFraction::from_rational(5, 1).depth(), 1);
+        assert_eq!(ContinuedFraction::from_rational(0, 3).depth(), 1);
+        assert_eq!(
+            ContinuedFraction {
+                coefficients: vec![2, 1]
+            }
+            .depth(),
+            2
+        );
+
+        // Consecutive Fibonacci numbers are the worst case for Euclid
+        let test_cases = [
+            (89, 55),
+            (-89, 55),
+            (1, 1),
+            (1, 2),
+            (514229, 317811),
+            (i32::MAX, 1),
+        ];
+
+        for (p, q) in test_cases {
+            let cf = ContinuedFraction::from_rational(p, q);
+            let smaller = p.unsigned_abs().min(q as u32);
+            let bound = 2 * smaller.checked_ilog2().unwrap_or(0) as usize + 2;
+
+            assert!(
+                cf.depth() <= bound,
+                "Depth {} exceeds {} for {}/{}",
+                cf.depth(),
+                bound,
+                p,
+                q
+            );
+            assert!(cf.coefficients.capacity() >= cf.depth());
+        }
+    }
+
+    #[test]
+    fn test_pell_solution() {
+        assert_eq!(ContinuedFraction::pell_solution(2), Some((3, 2)));
+        assert_eq!(ContinuedFraction::pell_solution(3), Some((2, 1)));
+        assert_eq!(ContinuedFraction::pell_solution(5), Some((9, 4)));
+        assert_eq!(ContinuedFraction::pell_solution(7), Some((8, 3)));
+        assert_eq!(ContinuedFraction::pell_solution(13), Some((649, 180)));
+        assert_eq!(
+            ContinuedFraction::pell_solution(61),
+            Some((1766319049, 226153980))
+        );
+
+        assert_eq!(ContinuedFraction::pell_solution(1), None);
+        assert_eq!(ContinuedFraction::pell_solution(4), None);
+        assert_eq!(ContinuedFraction::pell_solution(49), None);
+
+        for d in 2..100u32 {
+            if let Some((x, y)) = ContinuedFraction::pell_solution(d) {
+                let (x, y, d) = (x as u128, y as u128, d as u128);
+                assert_eq!(x * x - d * y * y, 1, "Pell failed for d = {}", d);
+            }
+        }
+
+        assert_eq!(
+            ContinuedFraction::from_sqrt(7).0.coefficients(),
+            &[2, 1, 1, 1, 4]
+        );
+        assert_eq!(ContinuedFraction::from_sqrt(7).1, 4);
+    }
+
+    #[test]
+    fn test_from_sqrt() {
+        let (cf, period) = ContinuedFraction::from_sqrt(2);
+        assert_eq!(cf.coefficients(), &[1, 2]);
+        assert_eq!(period, 1);
+
+        let test_cases: [(u32, &[i32]); 6] = [
+            (3, &[1, 1, 2]),
+            (7, &[2, 1, 1, 1, 4]),
+            (13, &[3, 1, 1, 1, 1, 6]),
+            (23, &[4, 1, 3, 1, 8]),
+            (61, &[7, 1, 4, 3, 1, 2, 2, 1, 3, 4, 1, 14]),
+            (9, &[3]),
+        ];
+
+        for (n, expected) in test_cases {
+            let (cf, period) = ContinuedFraction::from_sqrt(n);
+            assert_eq!(
+                cf.coefficients(),
+                expected,
+                "Expansion failed for sqrt({})",
+                n
+            );
+            assert_eq!(period, expected.len() - 1);
+        }
+
+        // The period is a palindrome closed by 2*a0
+        for n in 2..200u32 {
+            let (cf, period) = ContinuedFraction::from_sqrt(n);
+            let c = cf.coefficients();
+            if period == 0 {
+                assert_eq!(c[0] * c[0], n as i32);
+                continue;
+            }
+
+            assert_eq!(c[period], 2 * c[0]);
+            let inner = &c[1..period];
+            assert!(
+                inner.iter().eq(inner.iter().rev()),
+                "Period of sqrt({}) not palindromic",
+                n
+            );
+        }
+
+        // Repeating the period gives better and better approximations
+        let (cf, _) = ContinuedFraction::from_sqrt(2);
+        let mut coefficients = vec![cf.coefficients()[0]];
+        coefficients.extend([2; 10]);
+        let cf = ContinuedFraction::from_coefficients(&coefficients).unwrap();
+        assert!((cf.truncate(6).to_f64() - 2f64.sqrt()).abs() < 1e-4);
+        assert!((cf.to_f64() - 2f64.sqrt()).abs() < 1e-7);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_khinchin() {
+        // [1; 1, 1, 1, 1, 1, 1, 1, 2]
         let cf = ContinuedFraction::from_rational(89, 55);
-        assert_eq!(cf.coefficients(), &[1, 1, 1, 1, 1, 1, 1, 1, 2]);
+        let mean = cf.khinchin_geometric_mean();
+        assert!((mean - 2f64.powf(1.0 / 8.0)).abs() < 1e-12);
+        assert!(mean > 1.0 && mean < 2.0);
+        assert!(!cf.khinchin_check());
+
+        let cf = ContinuedFraction::from_coefficients(&[0, 1, 2, 3, 4, 5]).unwrap();
+        assert!((cf.khinchin_geometric_mean() - 120f64.powf(0.2)).abs() < 1e-12);
+        assert!(cf.khinchin_check());
+
+        let cf = ContinuedFraction::from_coefficients(&[3, 7, 15, 1, 292]).unwrap();
+        assert!(!cf.khinchin_check());
+
+        assert!(
+            ContinuedFraction::from(4)
+                .khinchin_geometric_mean()
+                .is_nan()
+        );
+        assert!(!ContinuedFraction::from(4).khinchin_check());
+    }
+
+    #[test]
+    fn test_convergent_matrix() {
+        let cf = ContinuedFraction::from_rational(103993, 33102);
+        let m = cf.convergent_matrix();
+        assert_eq!(m, ConvMatrix([[103993, 355], [33102, 113]]));
+        assert_eq!(m.convergent(), cf.to_rational());
+
+        let test_cases = [(22, 7), (89, 55), (-3, 7), (5, 1), (355, 113)];
+
+        for (p, q) in test_cases {
+            let cf = ContinuedFraction::from_rational(p, q);
+            let convergents = cf.convergents();
+            let ConvMatrix([[h, h_prev], [k, k_prev]]) = cf.convergent_matrix();
+
+            assert_eq!((h, k), convergents[convergents.len() - 1]);
+            if convergents.len() > 1 {
+                assert_eq!((h_prev, k_prev), convergents[convergents.len() - 2]);
+            }
+            // The determinant alternates in sign
+            assert_eq!((h * k_prev - h_prev * k).abs(), 1);
+        }
+
+        let empty = ContinuedFraction {
+            coefficients: vec![],
+        };
+        assert_eq!(empty.convergent_matrix(), ConvMatrix::IDENTITY);
+
+        // sqrt(2) = [1; 2, 2, ...], repeat the period by exponentiation
+        let (sqrt2, _) = ContinuedFraction::from_sqrt(2);
+        let deep = ConvMatrix::coefficient(1) * ConvMatrix::coefficient(2).pow(10);
+        let mut coefficients = vec![sqrt2.coefficients()[0]];
+        coefficients.extend([2; 10]);
+        assert_eq!(
+            deep,
+            ContinuedFraction::from_coefficients(&coefficients)
+                .unwrap()
+                .convergent_matrix()
+        );
+        assert_eq!(ConvMatrix::coefficient(3).pow(0), ConvMatrix::IDENTITY);
+    }
+
+    #[test]
+    fn test_period() {
+        let sqrt2 = ContinuedFraction::from_coefficients(&[1, 2, 2, 2, 2]).unwrap();
+        assert_eq!(sqrt2.period(), Some(1));
+
+        let sqrt7 = ContinuedFraction::from_coefficients(&[2, 1, 1, 1, 4, 1, 1, 1, 4]).unwrap();
+        assert_eq!(sqrt7.period(), Some(4));
+
+        // A cut short final block still counts
+        let sqrt7 =
+            ContinuedFraction::from_coefficients(&[2, 1, 1, 1, 4, 1, 1, 1, 4, 1, 1]).unwrap();
+        assert_eq!(sqrt7.period(), Some(4));
+
+        let (sqrt3, _) = ContinuedFraction::from_sqrt(3);
+        assert_eq!(sqrt3.period(), None);
+
+        // One stored period, the same coefficients as 3/2, only `from_sqrt` knows it repeats
+        let (sqrt2, period) = ContinuedFraction::from_sqrt(2);
+        assert_eq!((sqrt2.coefficients(), period), (&[1, 2][..], 1));
+        assert_eq!(sqrt2.period(), None);
+        assert_eq!(sqrt2, ContinuedFraction::from_rational(3, 2));
+
+        // Storing the period a second time makes it detectable
+        let mut coefficients = sqrt2.coefficients().to_vec();
+        coefficients.extend_from_slice(&sqrt2.coefficients()[1..]);
+        let sqrt2 = ContinuedFraction::from_coefficients(&coefficients).unwrap();
+        assert_eq!(sqrt2.period(), Some(period));
+
+        assert_eq!(ContinuedFraction::from_rational(22, 7).period(), None);
+        assert_eq!(
+            ContinuedFraction::from_rational(103993, 33102).period(),
+            None
+        );
+        assert_eq!(ContinuedFraction::from(5).period(), None);
+        assert_eq!(
+            ContinuedFraction {
+                coefficients: vec![]
+            }
+            .period(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_in_interval() {
+        let cf = ContinuedFraction::from_rational(22, 7);
+        assert!(cf.in_interval((3, 1), (4, 1), false));
+        assert!(cf.in_interval((311, 99), (22, 7), true));
+        assert!(!cf.in_interval((311, 99), (22, 7), false));
+        assert!(!cf.in_interval((333, 106), (355, 113), true));
+        assert!(cf.in_interval((-3, -1), (-4, -1), false));
+        assert!(!cf.in_interval((4, 1), (3, 1), true));
+
+        // Degenerate interval lo == hi
+        assert!(cf.in_interval((22, 7), (22, 7), true));
+        assert!(cf.in_interval((44, 14), (22, 7), true));
+        assert!(!cf.in_interval((22, 7), (22, 7), false));
+
+        // Neighbours closer together than f32 can tell apart
+        let cf = ContinuedFraction::from_rational(100_000, 99_999);
+        assert!(!cf.in_interval((99_999, 99_998), (2, 1), true));
+        assert!(cf.in_interval((1, 1), (99_999, 99_998), false));
+
+        let cf = ContinuedFraction::from_rational(-1, 3);
+        assert!(cf.in_interval((-1, 2), (0, 1), false));
+        assert!(cf.in_interval((1, -3), (1, 3), true));
+    }
+
+    #[test]
+    fn test_best_one_sided_approximation() {
+        let pi = ContinuedFraction {
+            coefficients: vec![3, 7, 15, 1, 292, 1, 1, 1, 2, 1, 3, 1],
+        };
+
+        assert_eq!(pi.best_lower_approximation(1), (3, 1));
+        assert_eq!(pi.best_upper_approximation(1), (4, 1));
+        assert_eq!(pi.best_upper_approximation(6), (19, 6));
+        assert_eq!(pi.best_upper_approximation(7), (22, 7));
+
+        // 333/106 needs q = 106, so below that the lower bound is a semi-convergent
+        assert_eq!(pi.best_lower_approximation(100), (311, 99));
+        assert_eq!(pi.best_upper_approximation(100), (22, 7));
+        assert_eq!(pi.best_lower_approximation(106), (333, 106));
+        assert_eq!(pi.best_upper_approximation(112), (22, 7));
+        assert_eq!(pi.best_upper_approximation(113), (355, 113));
+        assert_eq!(pi.best_lower_approximation(113), (333, 106));
+
+        // Brute force over every denominator against the exact value P/Q
+        let (big_p, big_q) = pi.to_rational_i64();
+        for max_denom in 1..300 {
+            let (p, q) = pi.best_lower_approximation(max_denom);
+            let (r, s) = pi.best_upper_approximation(max_denom);
+            assert!(q <= max_denom && s <= max_denom);
+            let (p, q, r, s) = (p as i64, q as i64, r as i64, s as i64);
+
+            for d in 1..=max_denom as i64 {
+                let below = (big_p * d).div_euclid(big_q);
+                let above = below + 1;
+                assert!(below * q <= p * d, "{}/{} beats {}/{}", below, d, p, q);
+                assert!(r * d <= above * s, "{}/{} beats {}/{}", above, d, r, s);
+            }
+        }
+
+        let cf = ContinuedFraction::from_rational(3, 7);
+        assert_eq!(cf.best_lower_approximation(7), (3, 7));
+        assert_eq!(cf.best_upper_approximation(7), (3, 7));
+        assert_eq!(cf.best_lower_approximation(3), (1, 3));
+        assert_eq!(cf.best_upper_approximation(3), (1, 2));
+
+        let cf = ContinuedFraction::from_rational(-22, 7);
+        assert_eq!(cf.best_lower_approximation(6), (-19, 6));
+        assert_eq!(cf.best_upper_approximation(6), (-3, 1));
+    }
+
+    #[test]
+    // Miri cannot execute the C code behind `gcd`
+    #[cfg_attr(miri, ignore)]
+    fn test_gcd_binary_matches_c() {
+        for a in -64..=64 {
+            for b in -64..=64 {
+                assert_eq!(gcd_binary(a, b), gcd(a, b), "Mismatch for ({}, {})", a, b);
+            }
+        }
+    }
+
+    #[test]
+    // Pure Rust, so this one also runs under Miri
+    fn test_gcd_binary() {
+        for a in 0..=64i32 {
+            for b in 0..=64i32 {
+                let d = gcd_binary(a, b);
+
+                // A common divisor that every other common divisor divides
+                if d == 0 {
+                    assert_eq!((a, b), (0, 0));
+                    continue;
+                }
+                assert!(a % d == 0 && b % d == 0, "({}, {}) -> {}", a, b, d);
+                assert!((1..=a.max(b)).all(|c| a % c != 0 || b % c != 0 || d % c == 0));
+            }
+        }
+
+        assert_eq!(gcd_binary(-12, 18), 6);
+        assert_eq!(gcd_binary(12, -18), 6);
+        assert_eq!(gcd_binary(-7, 0), 7);
+        assert_eq!(gcd_binary(i32::MAX, i32::MAX - 1), 1);
+        assert_eq!(gcd_binary(i32::MIN, 6), 2);
+        assert_eq!(gcd_binary(1 << 30, 3 << 20), 1 << 20);
+        assert!(std::panic::catch_unwind(|| gcd_binary(i32::MIN, 0)).is_err());
+    }
+
+    #[test]
+    fn test_rat_pair() {
+        let pair = RatPair { n: 22, m: 7 };
+        assert_eq!(ContinuedFraction::from(pair).coefficients(), &[3, 7]);
+        assert_eq!(
+            ContinuedFraction::from(RatPair { n: 3, m: -7 }),
+            ContinuedFraction::from_rational(-3, 7)
+        );
+
+        // The layout matches, so the C side accepts it as a Pair
+        let mut pair = RatPair { n: 48, m: 18 };
+        let g = unsafe { bindings::gcd(&mut pair as *mut RatPair as *mut bindings::Pair) };
+        assert_eq!(g, 6);
+    }
+
+    #[test]
+    fn test_scale() {
+        let test_cases = [
+            (22, 7),
+            (3, 7),
+            (-3, 7),
+            (89, 55),
+            (5, 1),
+            (1, 9),
+            (355, 113),
+        ];
+
+        for (p, q) in test_cases {
+            let cf = ContinuedFraction::from_rational(p, q);
+
+            for k in [-5, -1, 0, 1, 2, 3, 7, 14] {
+                let scaled = cf.scale(k);
+                let expected = ContinuedFraction::from_rational(k * p, q);
+
+                assert_eq!(
+                    scaled.coefficients(),
+                    expected.coefficients(),
+                    "{} * {}/{}",
+                    k,
+                    p,
+                    q
+                );
+                assert!(scaled.is_normalized());
+            }
+        }
+
+        let cf = ContinuedFraction::from_rational(3, 7);
+        assert_eq!(cf.scale(3).to_rational(), (9, 7));
+        assert_eq!(cf.scale(7).to_rational(), (3, 1));
+        assert_eq!(cf.scale(0).coefficients(), &[0]);
+    }
+
+    // `i32::MIN` has no positive counterpart, `from_rational` does not support it
+    proptest::proptest! {
+        #![proptest_config(proptest::prelude::ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn test_prop_to_rational(p in any::<i32>(), q in any::<i32>()) {
+            proptest::prop_assume!(p != i32::MIN && q != i32::MIN && q != 0);
+
+            let g = gcd_binary(p, q);
+            let (p_r, q_r) = if q < 0 { (-p / g, -q / g) } else { (p / g, q / g) };
+
+            let cf = ContinuedFraction::from_rational(p, q);
+            proptest::prop_assert_eq!(cf.to_rational(), (p_r, q_r));
+        }
+
+        #[test]
+        fn test_prop_convergents(p in any::<i32>(), q in 1i32..) {
+            proptest::prop_assume!(p != i32::MIN);
+
+            let convergents = ContinuedFraction::from_rational(p, q).convergents();
+
+            for &(h, k) in &convergents {
+                proptest::prop_assert_eq!(gcd_binary(h, k), 1);
+            }
+
+            // k_1 = a1 * k_0 may equal k_0 when a1 == 1, after that they strictly grow
+            for (i, w) in convergents.windows(2).enumerate() {
+                if i == 0 {
+                    proptest::prop_assert!(w[0].1 <= w[1].1);
+                } else {
+                    proptest::prop_assert!(w[0].1 < w[1].1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    // Runs against whichever gcd is linked, so run it with and without `asm-gcd` on riscv64:
+    //     cargo test --features asm-gcd test_gcd_paths
+    // as the riscv64 CI job does for both builds. Miri cannot execute the linked code
+    #[cfg_attr(miri, ignore)]
+    fn test_gcd_paths() {
+        let test_cases = [
+            (1071, 462),
+            (832040, 514229),
+            (1 << 20, 3 << 12),
+            (99991, 7),
+            (360, 360),
+        ];
+
+        for (n, m) in test_cases {
+            assert_eq!(gcd(n, m), gcd_binary(n, m), "Mismatch for ({}, {})", n, m);
+        }
+
+        // The C lcm goes through the linked gcd as well
+        assert_eq!(lcm(1071, 462), 23562);
+
+        for n in 1..=300 {
+            assert_eq!(gcd(n, 210), gcd_binary(n, 210));
+        }
+
+        // Call the symbol directly, `gcd` answers zero arguments before reaching it. The C
+        // code takes unsigned magnitudes while the assembly negates a signed remainder, so
+        // zeros and signs are where the two could differ
+        let linked = |n, m| unsafe { bindings::gcd(&mut bindings::Pair { n, m }) };
+        for (n, m) in [
+            (0, 7),
+            (7, 0),
+            (0, 0),
+            (0, -7),
+            (-7, 0),
+            (-1071, 462),
+            (1071, -462),
+            (-1071, -462),
+            (-1, 1),
+            (i32::MAX, -i32::MAX),
+            (-(1 << 20), 3 << 12),
+        ] {
+            assert_eq!(
+                linked(n, m),
+                gcd_binary(n, m),
+                "Mismatch for ({}, {})",
+                n,
+                m
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_stern_brocot() {
+        let cf = ContinuedFraction::from_rational(22, 7);
+        assert_eq!(cf.encode_stern_brocot(), (2 << 60) | 0x73);
+        assert_eq!(ContinuedFraction::decode_stern_brocot((2 << 60) | 0x73), cf);
+
+        for (p, q) in farey_sequence(5) {
+            let cf = ContinuedFraction::from_rational(p, q);
+            let code = cf.encode_stern_brocot();
+            let decoded = ContinuedFraction::decode_stern_brocot(code);
+
+            assert_eq!(
+                decoded.coefficients(),
+                cf.coefficients(),
+                "Roundtrip failed for {}/{}",
+                p,
+                q
+            );
+            assert_eq!(decoded.encode_stern_brocot(), code);
+        }
+
+        // Equal fractions share an address
+        let cf = ContinuedFraction {
+            coefficients: vec![3, 6, 1],
+        };
+        assert_eq!(cf.encode_stern_brocot(), (2 << 60) | 0x73);
+
+        assert!(
+            std::panic::catch_unwind(
+                || ContinuedFraction::from_rational(1, 16).encode_stern_brocot()
+            )
+            .is_err()
+        );
+        assert!(std::panic::catch_unwind(|| ContinuedFraction::decode_stern_brocot(0)).is_err());
+        assert!(
+            std::panic::catch_unwind(|| ContinuedFraction::decode_stern_brocot((2 << 60) | 0x01))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_notation() {
+        let cf = ContinuedFraction::from_rational(22, 7);
+        assert_eq!(cf.to_inline_notation(), "3 + 1/7");
+        assert_eq!(cf.to_latex(), "3 + \\cfrac{1}{7}");
+
+        let cf = ContinuedFraction::from_rational(89, 55);
+        assert_eq!(
+            cf.to_inline_notation(),
+            "1 + 1/(1 + 1/(1 + 1/(1 + 1/(1 + 1/(1 + 1/(1 + 1/(1 + 1/2)))))))"
+        );
+        assert_eq!(
+            cf.to_latex(),
+            "1 + \\cfrac{1}{1 + \\cfrac{1}{1 + \\cfrac{1}{1 + \\cfrac{1}{1 + \\cfrac{1}{1 + \
+             \\cfrac{1}{1 + \\cfrac{1}{1 + \\cfrac{1}{2}}}}}}}}"
+        );
+
+        let cf = ContinuedFraction::from_coefficients(&[3, 7, 15, 1]).unwrap();
+        assert_eq!(cf.to_inline_notation(), "3 + 1/(7 + 1/(15 + 1/1))");
+
+        let cf = ContinuedFraction::from_rational(-3, 7);
+        assert_eq!(cf.to_inline_notation(), "-1 + 1/(1 + 1/(1 + 1/3))");
+
+        assert_eq!(ContinuedFraction::from(5).to_inline_notation(), "5");
+        assert_eq!(ContinuedFraction::from(5).to_latex(), "5");
+
+        let empty = ContinuedFraction {
+            coefficients: vec![],
+        };
+        assert_eq!(empty.to_inline_notation(), "0");
+        assert_eq!(empty.to_latex(), "0");
+    }
+
+    #[test]
+    fn test_reduce_to_denom() {
+        let pi = ContinuedFraction {
+            coefficients: vec![3, 7, 15, 1, 292, 1, 1, 1, 2, 1, 3, 1],
+        };
+
+        // Closer than the last convergent that fits, 22/7
+        assert_eq!(pi.reduce_to_denom(100), (311, 99));
+        assert_eq!(pi.reduce_to_denom(57), (179, 57));
+        assert_eq!(pi.reduce_to_denom(1000), (355, 113));
+
+        for max_q in 1..400 {
+            assert_eq!(
+                pi.reduce_to_denom(max_q),
+                pi.best_approximation(max_q),
+                "q <= {}",
+                max_q
+            );
+        }
+
+        let test_cases = [(3, 7), (-3, 7), (89, 55), (1, 9), (13, 4), (-22, 7)];
+
+        for (p, q) in test_cases {
+            let cf = ContinuedFraction::from_rational(p, q);
+            for max_q in 1..20 {
+                let (r, s) = cf.reduce_to_denom(max_q);
+                let (t, u) = cf.best_approximation(max_q);
+
+                // Only ties may differ, so both must be equally close
+                let d1 = ((p * s - r * q) as i64 * u as i64).abs();
+                let d2 = ((p * u - t * q) as i64 * s as i64).abs();
+                assert_eq!(d1, d2, "{}/{} with q <= {}", p, q, max_q);
+            }
+            assert_eq!(cf.reduce_to_denom(q), (p, q));
+        }
+
+        // 5/2 is halfway between 2 and 3: best_approximation keeps the convergent 2,
+        // the Stern-Brocot search rounds half up
+        let cf = ContinuedFraction::from_rational(5, 2);
+        assert_eq!(cf.best_approximation(1), (2, 1));
+        assert_eq!(cf.reduce_to_denom(1), (3, 1));
+
+        assert_eq!(ContinuedFraction::from(4).reduce_to_denom(1), (4, 1));
+    }
+
+    #[test]
+    fn test_cf_e() {
+        assert_eq!(
+            ContinuedFraction::cf_e(15).coefficients(),
+            &[2, 1, 2, 1, 1, 4, 1, 1, 6, 1, 1, 8, 1, 1, 10]
+        );
+        assert_eq!(ContinuedFraction::cf_e(1).coefficients(), &[2]);
+
+        let e = ContinuedFraction::cf_e(20).to_f64();
+        assert!((e - std::f64::consts::E).abs() / std::f64::consts::E < 1e-10);
+
+        assert_eq!(ContinuedFraction::cf_e(4).to_rational(), (11, 4));
+        assert!(std::panic::catch_unwind(|| ContinuedFraction::cf_e(0)).is_err());
+    }
+
+    #[test]
+    fn test_phi() {
+        let phi = ContinuedFraction::phi(10);
+        assert_eq!(phi.coefficients(), &[1; 10]);
+        assert!(phi.is_golden_ratio_approx());
+
+        // F_1, F_2, ... = 1, 1, 2, 3, 5, ...
+        let mut fibonacci = vec![1, 1];
+        for i in 2..12 {
+            fibonacci.push(fibonacci[i - 1] + fibonacci[i - 2]);
+        }
+
+        for (n, &(h, k)) in phi.convergents().iter().enumerate() {
+            assert_eq!((h, k), (fibonacci[n + 1], fibonacci[n]), "Convergent {}", n);
+        }
+
+        let golden = (1.0 + 5f64.sqrt()) / 2.0;
+        assert!((ContinuedFraction::phi(30).to_f64() - golden).abs() < 1e-12);
+
+        assert!(!ContinuedFraction::from_rational(89, 55).is_golden_ratio_approx());
+        assert!(!ContinuedFraction::from_rational(22, 7).is_golden_ratio_approx());
+        assert!(ContinuedFraction::one().is_golden_ratio_approx());
+        assert!(std::panic::catch_unwind(|| ContinuedFraction::phi(0)).is_err());
+    }
+
+    #[test]
+    fn test_convergents_iter() {
+        let test_cases = [
+            (3, 1),
+            (1, 2),
+            (89, 55),
+            (3, 7),
+            (22, 7),
+            (1, 3),
+            (5, 1),
+            (-3, 7),
+            (355, 113),
+        ];
+
+        for (p, q) in test_cases {
+            let cf = ContinuedFraction::from_rational(p, q);
+            let lazy: Vec<_> = cf.convergents_iter().collect();
+
+            assert_eq!(lazy, cf.convergents(), "Mismatch for {}/{}", p, q);
+            assert_eq!(cf.convergents_iter().len(), cf.depth());
+        }
+
+        // Stops early without touching the rest
+        let pi = ContinuedFraction::from_coefficients(&[3, 7, 15, 1, 292]).unwrap();
+        let first = pi.convergents_iter().find(|&(_, k)| k > 100);
+        assert_eq!(first, Some((333, 106)));
+
+        let empty = ContinuedFraction {
+            coefficients: vec![],
+        };
+        assert_eq!(empty.convergents_iter().next(), None);
+    }
+
+    #[test]
+    fn test_gcd_checked() {
+        assert_eq!(gcd_checked(48, 18), Ok(6));
+        assert_eq!(gcd_checked(-48, 18), Ok(6));
+        assert_eq!(gcd_checked(48, -18), Ok(6));
+        assert_eq!(gcd_checked(0, -7), Ok(7));
+        assert_eq!(gcd_checked(0, 0), Ok(0));
+        assert_eq!(gcd_checked(i32::MAX, 1), Ok(1));
+        assert_eq!(gcd_checked(i32::MIN, 0), Err(GcdError::Overflow));
+        assert_eq!(gcd_checked(4, i32::MIN), Err(GcdError::Overflow));
+        assert_eq!(GcdError::Overflow.to_string(), "gcd input overflows i32");
+
+        #[cfg(debug_assertions)]
+        assert!(std::panic::catch_unwind(|| gcd(i32::MIN, 0)).is_err());
+    }
+
+    #[test]
+    fn test_distance() {
+        let a = ContinuedFraction::from_rational(22, 7);
+        let b = ContinuedFraction::from_rational(355, 113);
+        assert_eq!(a.distance(&b), (1, 791));
+        assert_eq!(b.distance(&a), (1, 791));
+
+        let c = ContinuedFraction::from_rational(-1, 2);
+        assert_eq!(c.distance(&ContinuedFraction::from_rational(1, 3)), (5, 6));
+        assert_eq!(a.distance(&a), (0, 1));
+        assert_eq!(a.distance(&ContinuedFraction::from(3)), (1, 7));
+
+        // Trailing 1 forms give the same value
+        let d = ContinuedFraction::from_coefficients(&[3, 6, 1]).unwrap();
+        assert_eq!(a.distance(&d), (0, 1));
+
+        let big = ContinuedFraction::from_rational(1, 65537);
+        let other = ContinuedFraction::from_rational(1, 65539);
+        assert_eq!(big.distance_checked(&other), None);
+        assert!(std::panic::catch_unwind(|| big.distance(&other)).is_err());
+    }
+
+    #[test]
+    fn test_max_denominator_in_convergents() {
+        // k_n = F_{n+1}, and F_46 is the largest Fibonacci number in i32
+        let phi = ContinuedFraction::phi(60);
+        assert_eq!(phi.max_denominator_in_convergents(), 1836311903);
+        assert_eq!(
+            ContinuedFraction::phi(10).max_denominator_in_convergents(),
+            55
+        );
+
+        let cf = ContinuedFraction::from_rational(103993, 33102);
+        assert_eq!(cf.max_denominator_in_convergents(), 33102);
+
+        let cf = ContinuedFraction::from_coefficients(&[0, 100_000, 100_000, 3]).unwrap();
+        assert_eq!(cf.max_denominator_in_convergents(), 100_000);
+        assert_eq!(
+            ContinuedFraction::from(7).max_denominator_in_convergents(),
+            1
+        );
+
+        let empty = ContinuedFraction {
+            coefficients: vec![],
+        };
+        assert_eq!(empty.max_denominator_in_convergents(), 0);
+    }
+
+    #[test]
+    fn test_try_from_vec() {
+        let coefficients = vec![3, 7, 15, 1];
+        let ptr = coefficients.as_ptr();
+        let cf = ContinuedFraction::try_from(coefficients).unwrap();
+        assert_eq!(cf.coefficients(), &[3, 7, 15, 1]);
+        // The vector was moved in, not copied
+        assert_eq!(cf.coefficients().as_ptr(), ptr);
+
+        let cf: ContinuedFraction = vec![-1, 1, 1, 3].try_into().unwrap();
+        assert_eq!(cf.to_rational(), (-3, 7));
+
+        assert_eq!(
+            ContinuedFraction::try_from(Vec::new()),
+            Err(InvalidCoefficients::Empty)
+        );
+        assert_eq!(
+            ContinuedFraction::try_from(vec![1, 2, 0, 3]),
+            Err(InvalidCoefficients::NonPositive { index: 2, value: 0 })
+        );
+    }
+
+    #[test]
+    fn test_concat() {
+        let ones = ContinuedFraction::phi(5).concat(&ContinuedFraction::phi(5));
+        assert_eq!(ones.coefficients(), &[1; 10]);
 
-        let test_cases = [(3, 7), (22, 7), (1, 3), (5, 1), (89, 55)];
+        let (sqrt2, _) = ContinuedFraction::from_sqrt(2);
+        let doubled = sqrt2.concat(&sqrt2);
+        assert_eq!(doubled.coefficients(), &[1, 2, 1, 2]);
+        assert!((doubled.to_f64() - 2f64.sqrt()).abs() < (sqrt2.to_f64() - 2f64.sqrt()).abs());
 
-        for (p, q) in test_cases {
+        // Appending the period itself extends the actual expansion of sqrt(2)
+        let period = sqrt2.tail(1);
+        let mut longer = sqrt2.clone();
+        for _ in 0..8 {
+            longer = longer.concat(&period);
+        }
+        assert_eq!(longer.coefficients(), &[1, 2, 2, 2, 2, 2, 2, 2, 2, 2]);
+        assert!((longer.to_f64() - 2f64.sqrt()).abs() < 1e-6);
+
+        let a = ContinuedFraction::from_rational(22, 7);
+        assert_ne!(a.concat(&a), a.clone() + a.clone());
+        assert!(
+            std::panic::catch_unwind(|| a.concat(&ContinuedFraction::from_rational(-1, 2)))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_repeat() {
+        let one = ContinuedFraction::from_coefficients(&[1]).unwrap();
+        let phi = one.repeat(20);
+        assert_eq!(
+            phi.coefficients(),
+            ContinuedFraction::phi(20).coefficients()
+        );
+
+        // F_11 / F_10
+        let cf = ContinuedFraction::from_rational(1, 1).repeat(10);
+        assert_eq!(cf.convergents().last(), Some(&(89, 55)));
+
+        let cf = ContinuedFraction::from_coefficients(&[1, 2]).unwrap();
+        assert_eq!(cf.repeat(3).coefficients(), &[1, 2, 1, 2, 1, 2]);
+        assert_eq!(cf.repeat(1).coefficients(), cf.coefficients());
+        assert_eq!(cf.repeat(0), ContinuedFraction::zero());
+
+        let negative = ContinuedFraction::from_rational(-1, 2);
+        assert_eq!(negative.repeat(1), negative);
+        assert!(std::panic::catch_unwind(|| negative.repeat(2)).is_err());
+    }
+
+    #[test]
+    fn test_simplest_in_interval() {
+        assert_eq!(
+            ContinuedFraction::simplest_in_interval((1, 3), (1, 2)),
+            (2, 5)
+        );
+        assert_eq!(
+            ContinuedFraction::simplest_in_interval((0, 1), (1, 1)),
+            (1, 2)
+        );
+        assert_eq!(
+            ContinuedFraction::simplest_in_interval((3, 1), (4, 1)),
+            (7, 2)
+        );
+        assert_eq!(
+            ContinuedFraction::simplest_in_interval((3, 1), (5, 1)),
+            (4, 1)
+        );
+        assert_eq!(
+            ContinuedFraction::simplest_in_interval((5, 2), (7, 1)),
+            (3, 1)
+        );
+        assert_eq!(
+            ContinuedFraction::simplest_in_interval((-5, 2), (7, 1)),
+            (0, 1)
+        );
+        assert_eq!(
+            ContinuedFraction::simplest_in_interval((-1, 2), (-1, 3)),
+            (-2, 5)
+        );
+        assert_eq!(
+            ContinuedFraction::simplest_in_interval((-1, 1), (0, 1)),
+            (-1, 2)
+        );
+        assert_eq!(
+            ContinuedFraction::simplest_in_interval((333, 106), (355, 113)),
+            (688, 219)
+        );
+        assert_eq!(
+            ContinuedFraction::simplest_in_interval((314, 100), (315, 100)),
+            (22, 7)
+        );
+
+        // Brute force: nothing with a smaller denominator fits
+        let test_cases = [
+            ((1, 7), (1, 6)),
+            ((5, 8), (2, 3)),
+            ((13, 10), (4, 3)),
+            ((21, 13), (13, 8)),
+        ];
+
+        for (lo, hi) in test_cases {
+            let (p, q) = ContinuedFraction::simplest_in_interval(lo, hi);
+            let inside = |p: i32, q: i32| lo.0 * q < p * lo.1 && p * hi.1 < hi.0 * q;
+            assert!(inside(p, q), "{}/{} outside {:?}..{:?}", p, q, lo, hi);
+
+            for s in 1..q {
+                assert!(
+                    (0..=2 * s).all(|r| !inside(r, s)),
+                    "{:?}..{:?} has q = {}",
+                    lo,
+                    hi,
+                    s
+                );
+            }
+        }
+
+        assert!(
+            std::panic::catch_unwind(|| ContinuedFraction::simplest_in_interval((1, 2), (1, 2)))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_linear_fractional() {
+        let maps = [
+            (1, 0, 0, 1),
+            (2, 3, 0, 1),
+            (1, 2, 3, 4),
+            (-5, 7, 2, -3),
+            (0, 1, 1, 0),
+            (3, -1, -2, 5),
+        ];
+
+        for (p, q) in [(3, 7), (22, 7), (-3, 7), (5, 1), (-5, 1), (89, 55), (0, 1)] {
             let cf = ContinuedFraction::from_rational(p, q);
-            let (p2, q2) = cf.to_rational();
 
-            assert_eq!(p * q2, p2 * q, "Roundtrip failed for {}/{}", p, q);
+            for (a, b, c, d) in maps {
+                let num = a * p + b * q;
+                let den = c * p + d * q;
+
+                if den == 0 {
+                    continue;
+                }
+
+                assert_eq!(
+                    cf.linear_fractional(a, b, c, d),
+                    ContinuedFraction::from_rational(num, den),
+                    "({}x + {}) / ({}x + {}) at x = {}/{}",
+                    a,
+                    b,
+                    c,
+                    d,
+                    p,
+                    q
+                );
+            }
+
+            assert_eq!(
+                cf.add_integer(4),
+                ContinuedFraction::from_rational(p + 4 * q, q)
+            );
+        }
+
+        let pole = std::panic::catch_unwind(|| {
+            ContinuedFraction::from_rational(1, 2).linear_fractional(1, 0, 2, -1)
+        });
+        assert!(pole.is_err());
+    }
+
+    #[test]
+    fn test_to_decimal_string() {
+        let test_cases = [
+            ((1, 3), 10, "0.3333333333"),
+            ((22, 7), 5, "3.14285"),
+            ((-22, 7), 5, "-3.14285"),
+            ((-1, 4), 4, "-0.2500"),
+            ((355, 113), 6, "3.141592"),
+            ((7, 2), 0, "3"),
+            ((-5, 1), 2, "-5.00"),
+            ((0, 1), 3, "0.000"),
+        ];
+
+        for ((p, q), places, expected) in test_cases {
+            assert_eq!(
+                ContinuedFraction::from_rational(p, q).to_decimal_string(places),
+                expected,
+                "{}/{} to {} places",
+                p,
+                q,
+                places
+            );
+        }
+    }
+
+    #[test]
+    fn test_repeating_decimal_period() {
+        let test_cases = [
+            ((1, 7), "0.", "142857"),
+            ((1, 4), "0.25", ""),
+            ((1, 6), "0.1", "6"),
+            ((22, 7), "3.", "142857"),
+            ((-1, 3), "-0.", "3"),
+            ((7, 12), "0.58", "3"),
+            ((1, 1), "1", ""),
+            ((0, 1), "0", ""),
+        ];
+
+        for ((p, q), non_repeating, repeating) in test_cases {
+            assert_eq!(
+                ContinuedFraction::from_rational(p, q).repeating_decimal_period(),
+                (non_repeating.to_string(), repeating.to_string()),
+                "{}/{}",
+                p,
+                q
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_partial_quotient_statistics() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-12;
+
+        // 89/55 = [1; 1, 1, 1, 1, 1, 1, 1, 2]
+        let (mean, variance, geometric) =
+            ContinuedFraction::from_rational(89, 55).partial_quotient_statistics();
+        assert!(close(mean, 9.0 / 8.0));
+        assert!(close(variance, 7.0 / 64.0));
+        assert!(close(geometric, 2f64.powf(1.0 / 8.0)));
+
+        let (mean, variance, geometric) = ContinuedFraction::from_coefficients(&[0, 1_000_000])
+            .unwrap()
+            .partial_quotient_statistics();
+        assert!(close(mean, 1e6));
+        assert!(close(variance, 0.0));
+        assert!((geometric - 1e6).abs() < 1e-6);
+
+        let (mean, variance, geometric) = ContinuedFraction::from_coefficients(&[2, 1, 2, 1, 2])
+            .unwrap()
+            .partial_quotient_statistics();
+        assert!(close(mean, 1.5));
+        assert!(close(variance, 0.25));
+        assert!(close(geometric, 2f64.sqrt()));
+
+        let (mean, variance, geometric) = ContinuedFraction::from(7).partial_quotient_statistics();
+        assert!(mean.is_nan() && variance.is_nan() && geometric.is_nan());
+    }
+
+    #[test]
+    fn test_compare_to_rational() {
+        let fractions = [
+            (3, 7),
+            (22, 7),
+            (-3, 7),
+            (355, 113),
+            (89, 55),
+            (-5, 1),
+            (0, 1),
+        ];
+        let rationals = [
+            (3, 7),
+            (6, 14),
+            (22, 7),
+            (-22, -7),
+            (333, 106),
+            (355, 113),
+            (1, 2),
+            (-1, 2),
+            (5, -1),
+            (144, 89),
+            (0, 5),
+        ];
+
+        for (p, q) in fractions {
+            let cf = ContinuedFraction::from_rational(p, q);
+
+            for (r, s) in rationals {
+                let expected = (p as f64 / q as f64)
+                    .partial_cmp(&(r as f64 / s as f64))
+                    .unwrap();
+
+                assert_eq!(
+                    cf.compare_to_rational(r, s),
+                    expected,
+                    "{}/{} vs {}/{}",
+                    p,
+                    q,
+                    r,
+                    s
+                );
+            }
+        }
+
+        // The empty fraction compares as 0
+        let empty = ContinuedFraction {
+            coefficients: vec![],
+        };
+        assert_eq!(empty.compare_to_rational(0, 5), Ordering::Equal);
+        assert_eq!(empty.compare_to_rational(1, 2), Ordering::Less);
+        assert_eq!(empty.compare_to_rational(1, -2), Ordering::Greater);
+
+        // Non-canonical coefficients of 3/2
+        let cf = ContinuedFraction::from_coefficients(&[1, 1, 1]).unwrap();
+        assert_eq!(cf.compare_to_rational(3, 2), Ordering::Equal);
+        assert_eq!(cf.compare_to_rational(2, 1), Ordering::Less);
+        assert_eq!(cf.compare_to_rational(1, 1), Ordering::Greater);
+    }
+
+    #[cfg(feature = "num-rational")]
+    #[test]
+    fn test_num_rational() {
+        use num_rational::Ratio;
+
+        for (p, q) in [
+            (3, 7),
+            (22, 7),
+            (-3, 7),
+            (6, 14),
+            (5, -10),
+            (0, 3),
+            (89, 55),
+        ] {
+            let ratio = Ratio::new(p, q);
+            let cf = ContinuedFraction::from(ratio);
+            assert_eq!(cf, ContinuedFraction::from_rational(p, q));
+
+            let back = Ratio::from(cf.clone());
+            assert_eq!(back, ratio);
+            assert_eq!(ContinuedFraction::from(back), cf);
+
+            // An unreduced raw ratio converts to the same value
+            let raw = Ratio::new_raw(2 * p, 2 * q);
+            assert_eq!(ContinuedFraction::from(raw), cf);
+            assert_eq!(Ratio::from(ContinuedFraction::from(raw)), ratio);
         }
+    }
 
+    #[test]
+    fn test_from_rational_zero_denominator() {
+        let err = std::panic::catch_unwind(|| ContinuedFraction::from_rational(3, 0)).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<String>().map(String::as_str),
+            Some("denominator must be non-zero, got from_rational(3, 0)")
+        );
+
+        assert_eq!(
+            ContinuedFraction::try_from_rational(3, 0),
+            Err(ContinuedFractionError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_convergent_parity() {
+        // 22/7 = [3; 7], 3/1 lies below and 22/7 is the value itself
         let cf = ContinuedFraction::from_rational(22, 7);
-        let conv = cf.convergents();
-        assert_eq!(conv, vec![(3, 1), (22, 7)]);
+        assert_eq!(cf.convergents(), vec![(3, 1), (22, 7)]);
+        assert_eq!(cf.convergent_parity(0), Parity::Even);
+        assert_eq!(cf.convergent_parity(1), Parity::Odd);
+
+        // The leading coefficients of pi, where 333/106 < pi < 355/113
+        let pi = ContinuedFraction::from_coefficients(&[3, 7, 15, 1, 292]).unwrap();
+        let value = pi.to_f64();
+
+        for (n, (h, k)) in pi.convergents().into_iter().enumerate() {
+            let approx = h as f64 / k as f64;
+
+            match pi.convergent_parity(n) {
+                Parity::Even => assert!(approx <= value, "{}/{} should be below", h, k),
+                Parity::Odd => assert!(approx >= value, "{}/{} should be above", h, k),
+            }
+        }
+
+        assert_eq!(pi.convergents()[2], (333, 106));
+        assert_eq!(pi.convergent_parity(2), Parity::Even);
+        assert_eq!(pi.convergents()[3], (355, 113));
+        assert_eq!(pi.convergent_parity(3), Parity::Odd);
+
+        assert!(std::panic::catch_unwind(|| cf.convergent_parity(2)).is_err());
+    }
+
+    #[test]
+    fn test_gcd64() {
+        assert_eq!(gcd64(48, 18), 6);
+        assert_eq!(gcd64(-48, 18), 6);
+        assert_eq!(gcd64(48, -18), 6);
+        assert_eq!(gcd64(0, -5), 5);
+        assert_eq!(gcd64(0, 0), 0);
+        assert_eq!(gcd64(1 << 40, 1 << 35), 1 << 35);
+        assert_eq!(gcd64(i64::MAX, i64::MAX), i64::MAX);
+
+        // Consecutive Fibonacci numbers beyond i32
+        assert_eq!(gcd64(12_586_269_025, 7_778_742_049), 1);
+        assert_eq!(gcd64(3 * 12_586_269_025, 3 * 7_778_742_049), 3);
+
+        for (n, m) in [(12i32, 8i32), (-7, 21), (0, 9), (100_000, 99_999)] {
+            assert_eq!(gcd64(n as i64, m as i64), gcd(n.abs(), m.abs()) as i64);
+        }
+
+        let cf = ContinuedFraction64::from_rational(2 * 12_586_269_025, 2 * 7_778_742_049);
+        assert_eq!(cf.to_rational(), (12_586_269_025, 7_778_742_049));
+    }
+
+    #[test]
+    fn test_convergent_denominators_fibonacci_bound() {
+        for (p, q) in [
+            (3, 7),
+            (22, 7),
+            (-3, 7),
+            (355, 113),
+            (1_346_269, 832_040),
+            (5, 1),
+        ] {
+            assert!(
+                ContinuedFraction::from_rational(p, q).convergent_denominators_fibonacci_bound(),
+                "{}/{}",
+                p,
+                q
+            );
+        }
+
+        // All ones meets the bound with equality, k_n = F_{n+1}
+        let phi = ContinuedFraction::from_coefficients(&[1; 40]).unwrap();
+        assert!(phi.convergent_denominators_fibonacci_bound());
+        assert_eq!(phi.convergents_i64()[39].1, 102_334_155);
+
+        // Long enough for the denominators to saturate
+        let long = ContinuedFraction::from_coefficients(&[1; 300]).unwrap();
+        assert!(long.convergent_denominators_fibonacci_bound());
+    }
+
+    #[test]
+    fn test_hurwitz_check() {
+        // Ratios of Fibonacci numbers, every other convergent meets the bound
+        let golden = ContinuedFraction::from_rational(89, 55);
+        assert!(golden.hurwitz_check());
+        assert!(ContinuedFraction::from_rational(5, 3).hurwitz_check());
+        assert!(!ContinuedFraction::from_rational(3, 2).hurwitz_check());
+
+        // Large coefficients make the preceding convergent a very good approximation
+        assert!(ContinuedFraction::from_rational(355, 113).hurwitz_check());
+        assert!(ContinuedFraction::from_rational(22, 7).hurwitz_check());
+
+        // 1/2 = [0; 2]: |1/2 - 0| = 1/2 is not below 1/sqrt(5)
+        assert!(!ContinuedFraction::from_rational(1, 2).hurwitz_check());
+        assert!(!ContinuedFraction::from(5).hurwitz_check());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_float_conversions() {
+        assert_eq!(
+            f64::from(ContinuedFraction::from_rational(1, 3)),
+            1.0_f64 / 3.0
+        );
+        assert_eq!(
+            f32::from(ContinuedFraction::from_rational(1, 3)),
+            1.0_f32 / 3.0
+        );
+        assert_eq!(
+            f64::from(ContinuedFraction::from_rational(-22, 7)),
+            -22.0 / 7.0
+        );
+
+        let pi = ContinuedFraction::try_from(std::f64::consts::PI).unwrap();
+        assert_eq!(&pi.coefficients()[..5], &[3, 7, 15, 1, 292]);
+        assert!(pi.depth() <= 20);
+
+        assert_eq!(
+            ContinuedFraction::try_from(0.5).unwrap().coefficients(),
+            &[0, 2]
+        );
+        assert_eq!(
+            ContinuedFraction::try_from(-3.0).unwrap().coefficients(),
+            &[-3]
+        );
+
+        for x in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 1e20] {
+            assert!(matches!(
+                ContinuedFraction::try_from(x),
+                Err(ContinuedFractionError::InvalidInput(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_convergent_at() {
+        for (p, q) in [
+            (3, 7),
+            (22, 7),
+            (-3, 7),
+            (355, 113),
+            (1_346_269, 832_040),
+            (5, 1),
+        ] {
+            let cf = ContinuedFraction::from_rational(p, q);
+            let convergents = cf.convergents();
+
+            for (n, &convergent) in convergents.iter().enumerate() {
+                assert_eq!(cf.convergent_at(n), Some(convergent));
+            }
+
+            assert_eq!(cf.convergent_at(convergents.len()), None);
+        }
+
+        // Convergents of [1; 1, ...] are Fibonacci ratios, F_47 overflows i32
+        let phi = ContinuedFraction::from_coefficients(&[1; 50]).unwrap();
+        assert_eq!(phi.convergent_at(44), Some((1_836_311_903, 1_134_903_170)));
+        assert_eq!(phi.convergent_at(45), None);
+        assert_eq!(phi.convergent_at(49), None);
+    }
+
+    #[test]
+    fn test_best_rational_in_farey_neighbors() {
+        // 1/pi = 0.3183..., between 2/7 and 1/3 in F_7
+        let inv_pi = ContinuedFraction::from_coefficients(&[0, 3, 7, 15, 1, 292]).unwrap();
+        assert_eq!(inv_pi.best_rational_in_farey_neighbors(7), ((2, 7), (1, 3)));
+
+        // Against the full Farey sequence, for values inside and outside of it
+        for n in 1..=12 {
+            let farey = farey_sequence(n);
+
+            for (p, q) in [
+                (1, 3),
+                (2, 7),
+                (3, 8),
+                (5, 13),
+                (1, 2),
+                (11, 12),
+                (1, 97),
+                (60, 97),
+            ] {
+                let cf = ContinuedFraction::from_rational(p, q);
+                let ((p1, q1), (p2, q2)) = cf.best_rational_in_farey_neighbors(n);
+
+                let below = farey
+                    .iter()
+                    .rev()
+                    .find(|&&(r, s)| (r as i64) * (q as i64) < (p as i64) * (s as i64));
+                let above = farey
+                    .iter()
+                    .find(|&&(r, s)| (r as i64) * (q as i64) > (p as i64) * (s as i64));
+
+                assert_eq!(below, Some(&(p1, q1)), "{}/{} in F_{}", p, q, n);
+                assert_eq!(above, Some(&(p2, q2)), "{}/{} in F_{}", p, q, n);
+                if q as u32 <= n {
+                    assert_eq!((p * q1 - p1 * q, p2 * q - p * q2), (1, 1));
+                } else {
+                    assert_eq!(p2 * q1 - p1 * q2, 1);
+                }
+            }
+        }
+
+        // Outside [0, 1] and at integers
+        let cf = ContinuedFraction::from_rational(-22, 7);
+        assert_eq!(cf.best_rational_in_farey_neighbors(7), ((-19, 6), (-3, 1)));
+        let cf = ContinuedFraction::from(2);
+        assert_eq!(cf.best_rational_in_farey_neighbors(5), ((9, 5), (11, 5)));
+    }
+
+    #[test]
+    fn test_safe_pair() {
+        let mut pair = SafePair::new(48, 18);
+        assert_eq!((pair.n(), pair.m()), (48, 18));
+        assert_eq!(pair.gcd(), 6);
+
+        for (n, m) in [(9, 3), (17, 13), (0, 5), (5, 0), (0, 0), (100, 25)] {
+            assert_eq!(SafePair::new(n, m).gcd(), gcd(n, m));
+        }
+
+        // Negative inputs take the absolute value first
+        for (n, m, d) in [(-48, 18, 6), (48, -18, 6), (-48, -18, 6)] {
+            assert_eq!(SafePair::new(n, m).gcd(), d);
+            assert_eq!((SafePair::new(n, m).n(), SafePair::new(n, m).m()), (n, m));
+        }
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(2, 3, 3, 5), Some(8));
+        assert_eq!(crt(0, 4, 0, 6), Some(0));
+        assert_eq!(crt(1, 4, 3, 6), Some(9));
+        assert_eq!(crt(-1, 7, 12, 13), Some(90));
+        assert_eq!(crt(5, 1, 3, 4), Some(3));
+
+        // gcd(4, 6) = 2 does not divide 2 - 1
+        assert_eq!(crt(1, 4, 2, 6), None);
+        assert_eq!(crt(1, 0, 2, 3), None);
+        assert_eq!(crt(1, 3, 2, -5), None);
+
+        // lcm close to i32::MAX still fits, beyond it the largest solutions do not
+        let x = crt(1, 46_337, 0, 46_327).unwrap();
+        assert_eq!((x % 46_337, x % 46_327), (1, 0));
+        assert_eq!(crt(65_536, 65_537, 65_538, 65_539), None);
+        assert_eq!(crt(1, 65_537, 1, 65_539), Some(1));
+
+        for (m1, m2) in [(3, 5), (4, 6), (12, 18), (7, 7), (9, 10)] {
+            let l = lcm(m1, m2);
+
+            for r1 in 0..m1 {
+                for r2 in 0..m2 {
+                    let brute = (0..l).find(|x| x % m1 == r1 && x % m2 == r2);
+                    assert_eq!(
+                        crt(r1, m1, r2, m2),
+                        brute,
+                        "{} mod {}, {} mod {}",
+                        r1,
+                        m1,
+                        r2,
+                        m2
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rationalize() {
+        let test_cases = [
+            ((0.333333, 0.0001), (1, 3)),
+            ((-0.333333, 0.0001), (-1, 3)),
+            ((std::f64::consts::PI, 0.01), (22, 7)),
+            ((std::f64::consts::PI, 0.001), (201, 64)),
+            ((std::f64::consts::PI, 1e-6), (355, 113)),
+            ((0.5, 0.0), (1, 2)),
+            ((2.0, 0.0), (2, 1)),
+            ((0.01, 0.02), (0, 1)),
+            ((1e6 + 0.5, 0.1), (2_000_001, 2)),
+            ((1.0 / 1000.0, 1e-9), (1, 1000)),
+        ];
+
+        for ((x, epsilon), expected) in test_cases {
+            let (p, q) = ContinuedFraction::rationalize(x, epsilon).unwrap();
+            assert_eq!((p, q), expected, "rationalize({}, {})", x, epsilon);
+            assert!((x - p as f64 / q as f64).abs() <= epsilon);
+        }
+
+        // Nothing with a smaller denominator is within the tolerance
+        let close_calls = [
+            (0.618034, 1e-4),
+            (std::f64::consts::E, 1e-5),
+            (-std::f64::consts::SQRT_2, 1e-7),
+        ];
+        for (x, epsilon) in close_calls {
+            let (_, q) = ContinuedFraction::rationalize(x, epsilon).unwrap();
+            for s in 1..q {
+                let r = (x * s as f64).round();
+                assert!(
+                    (x - r / s as f64).abs() > epsilon,
+                    "{} / {} is closer",
+                    r,
+                    s
+                );
+            }
+        }
+
+        assert_eq!(
+            ContinuedFraction::rationalize(f64::NAN, 0.1),
+            Err(RationalizeError::NotFinite)
+        );
+        assert_eq!(
+            ContinuedFraction::rationalize(1.0, f64::INFINITY),
+            Err(RationalizeError::NotFinite)
+        );
+        assert_eq!(
+            ContinuedFraction::rationalize(1.0, -0.1),
+            Err(RationalizeError::NegativeEpsilon)
+        );
+        assert_eq!(
+            ContinuedFraction::rationalize(1e10, 0.1),
+            Err(RationalizeError::Overflow)
+        );
+        assert_eq!(
+            ContinuedFraction::rationalize(1.0 / 3e9, 0.0),
+            Err(RationalizeError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_to_egyptian_binary() {
+        assert_eq!(
+            ContinuedFraction::zero().to_egyptian_binary(),
+            Vec::<i32>::new()
+        );
+        assert_eq!(
+            ContinuedFraction::from_rational(2, 3).to_egyptian_binary(),
+            vec![2, 6]
+        );
+        assert_eq!(
+            ContinuedFraction::from_rational(3, 8).to_egyptian_binary(),
+            vec![4, 8]
+        );
+        assert_eq!(
+            ContinuedFraction::from_rational(4, 13).to_egyptian_binary(),
+            vec![4, 26, 52]
+        );
+
+        // 5/121 is the classic case where the greedy expansion explodes
+        let test_cases = [
+            (3, 7),
+            (5, 6),
+            (7, 15),
+            (6, 7),
+            (21, 23),
+            (1, 2),
+            (5, 121),
+            (99, 100),
+        ];
+
+        for (p, q) in test_cases {
+            let denominators = ContinuedFraction::from_rational(p, q).to_egyptian_binary();
+
+            assert!(denominators.windows(2).all(|w| w[0] < w[1]));
+
+            // Sum the unit fractions exactly
+            let (mut num, mut den) = (0i128, 1i128);
+            for &n in &denominators {
+                (num, den) = (num * n as i128 + den, den * n as i128);
+                let d = gcd_i128(num, den);
+                (num, den) = (num / d, den / d);
+            }
+
+            assert_eq!((num, den), (p as i128, q as i128), "{}/{}", p, q);
+        }
+
+        let fraction = ContinuedFraction::from_rational(4, 13);
+        let greedy = fraction.egyptian_fraction();
+        let binary = fraction.to_egyptian_binary();
+        assert_eq!(cmp_egyptian_fractions(&binary, &greedy), Ordering::Less);
+        assert_eq!(cmp_egyptian_fractions(&greedy, &binary), Ordering::Greater);
+        assert_eq!(cmp_egyptian_fractions(&[2, 3], &[2, 3]), Ordering::Equal);
+        assert_eq!(cmp_egyptian_fractions(&[3], &[2, 6]), Ordering::Less);
+    }
+
+    #[test]
+    fn test_from_rational_i32_min() {
+        let cf = ContinuedFraction::from_rational(i32::MIN, 2);
+        assert_eq!(cf.to_rational(), (i32::MIN / 2, 1));
+
+        let cf = ContinuedFraction::from_rational(i32::MIN, 3);
+        assert_eq!(cf.coefficients(), &[-715_827_883, 3]);
+        assert_eq!(cf.to_rational(), (i32::MIN, 3));
+
+        let cf = ContinuedFraction::from_rational(i32::MIN, i32::MIN);
+        assert_eq!(cf.to_rational(), (1, 1));
+
+        let cf = ContinuedFraction::from_rational(6, i32::MIN);
+        assert_eq!(cf.to_rational(), (-3, 1 << 30));
+
+        let cf = ContinuedFraction::from_rational(i32::MIN, -2);
+        assert_eq!(cf.to_rational(), (1 << 30, 1));
+    }
+
+    #[test]
+    fn test_stern_brocot_depth() {
+        // 3/7 = [0; 2, 3], one level above the sum 0 + 2 + 3
+        assert_eq!(
+            ContinuedFraction::from_rational(3, 7).stern_brocot_depth(),
+            4
+        );
+        assert_eq!(ContinuedFraction::one().stern_brocot_depth(), 0);
+        assert_eq!(
+            ContinuedFraction::from_rational(1, 2).stern_brocot_depth(),
+            1
+        );
+        assert_eq!(ContinuedFraction::from(5).stern_brocot_depth(), 4);
+
+        for (p, q) in [(3, 7), (22, 7), (355, 113), (89, 55), (1, 100), (13, 8)] {
+            let cf = ContinuedFraction::from_rational(p, q);
+            assert_eq!(
+                cf.stern_brocot_depth(),
+                cf.stern_brocot_path().len() as u64,
+                "{}/{}",
+                p,
+                q
+            );
+        }
+
+        // Deeper than any path that fits in memory
+        let deep = ContinuedFraction::from_coefficients(&[i32::MAX, i32::MAX, i32::MAX]).unwrap();
+        assert_eq!(deep.stern_brocot_depth(), 3 * i32::MAX as u64 - 1);
+
+        assert!(
+            std::panic::catch_unwind(|| ContinuedFraction::zero().stern_brocot_depth()).is_err()
+        );
+        assert!(
+            std::panic::catch_unwind(
+                || ContinuedFraction::from_rational(-1, 2).stern_brocot_depth()
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_coefficient_sum_product() {
+        let test_cases = [
+            ((3, 7), 5, 0),
+            ((22, 7), 10, 21),
+            ((355, 113), 26, 336),
+            ((-3, 7), 4, -3),
+            ((89, 55), 10, 2),
+            ((5, 1), 5, 5),
+        ];
+
+        for ((p, q), sum, product) in test_cases {
+            let cf = ContinuedFraction::from_rational(p, q);
+            assert_eq!(cf.coefficient_sum(), sum, "{}/{}", p, q);
+            assert_eq!(cf.coefficient_product(), product, "{}/{}", p, q);
+        }
+
+        let empty = ContinuedFraction {
+            coefficients: Vec::new(),
+        };
+        assert_eq!(empty.coefficient_sum(), 0);
+        assert_eq!(empty.coefficient_product(), 1);
+
+        // Neither fits in i32
+        let big = ContinuedFraction::from_coefficients(&[i32::MAX, i32::MAX]).unwrap();
+        assert_eq!(big.coefficient_sum(), 2 * i32::MAX as i64);
+        assert_eq!(big.coefficient_product(), i32::MAX as i64 * i32::MAX as i64);
+    }
+
+    #[test]
+    fn test_gauss_kuzmin_distribution() {
+        // 355/113 = [3; 7, 16]
+        assert_eq!(
+            ContinuedFraction::from_rational(355, 113).gauss_kuzmin_distribution(),
+            vec![(7, 0.5), (16, 0.5)]
+        );
+        assert_eq!(
+            ContinuedFraction::from_coefficients(&[0, 1, 2, 1, 1, 3])
+                .unwrap()
+                .gauss_kuzmin_distribution(),
+            vec![(1, 0.6), (2, 0.2), (3, 0.2)]
+        );
+        assert!(
+            ContinuedFraction::from(4)
+                .gauss_kuzmin_distribution()
+                .is_empty()
+        );
+
+        // Truncations of phi normalize to [1; 1, ..., 1, 2]
+        let phi = ContinuedFraction::from_coefficients(&[1; 21]).unwrap();
+        assert_eq!(
+            phi.gauss_kuzmin_distribution(),
+            vec![(1, 18.0 / 19.0), (2, 1.0 / 19.0)]
+        );
+
+        let total: f64 = phi
+            .gauss_kuzmin_distribution()
+            .iter()
+            .map(|&(_, f)| f)
+            .sum();
+        assert!((total - 1.0).abs() < 1e-12);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_gauss_kuzmin_probability() {
+        assert!(
+            (ContinuedFraction::gauss_kuzmin_probability(1) - (4.0f64 / 3.0).log2()).abs() < 1e-15
+        );
+        assert!(
+            (ContinuedFraction::gauss_kuzmin_probability(2) - (9.0f64 / 8.0).log2()).abs() < 1e-15
+        );
+
+        let total: f64 = (1..100_000)
+            .map(ContinuedFraction::gauss_kuzmin_probability)
+            .sum();
+        assert!((total - 1.0).abs() < 1e-4);
+
+        // phi puts almost all of its weight on 1, far above the expected ~41.5%
+        let phi = ContinuedFraction::from_coefficients(&[1; 21]).unwrap();
+        let (k, frequency) = phi.gauss_kuzmin_distribution()[0];
+        assert_eq!(k, 1);
+        assert!(frequency - ContinuedFraction::gauss_kuzmin_probability(1) > 0.5);
+    }
+
+    #[test]
+    fn test_into_rational_tuple() {
+        for (p, q) in [(3, 7), (22, 7), (-3, 7), (6, 14), (5, -1), (0, 3)] {
+            let cf = ContinuedFraction::from_rational(p, q);
+
+            let borrowed: (i32, i32) = (&cf).into();
+            assert_eq!(borrowed, cf.to_rational());
+
+            let expected = cf.to_rational();
+            let (r, s): (i32, i32) = cf.into();
+            assert_eq!((r, s), expected);
+        }
+    }
+
+    #[test]
+    fn test_builder() {
+        let mut builder = ContinuedFraction::with_capacity(4);
+        builder.push(3).push(7).push(15);
+        builder.push(1);
+
+        let cf = builder.build().unwrap();
+        assert_eq!(cf.coefficients(), &[3, 7, 15, 1]);
+        assert_eq!(cf.to_rational(), (355, 113));
+
+        let mut builder = ContinuedFraction::with_capacity(2);
+        for a in [0, 1, 2, 3, 4] {
+            builder.push(a);
+        }
+        assert_eq!(builder.build().unwrap().coefficients(), &[0, 1, 2, 3, 4]);
+
+        assert_eq!(
+            ContinuedFraction::with_capacity(1).build(),
+            Err(InvalidCoefficients::Empty)
+        );
+
+        let mut builder = ContinuedFraction::with_capacity(3);
+        builder.push(1).push(2).push(-3);
+        assert_eq!(
+            builder.build(),
+            Err(InvalidCoefficients::NonPositive {
+                index: 2,
+                value: -3
+            })
+        );
+    }
+
+    #[test]
+    fn test_pade_approximant() {
+        // 1/(1 - x) = 1 + x + x^2 + ...
+        assert_eq!(
+            ContinuedFraction::pade_approximant(&[1; 6], 0, 1),
+            Some((vec![1], vec![1, -1]))
+        );
+
+        // Fibonacci generating function 1/(1 - x - x^2)
+        let fibonacci = [1, 1, 2, 3, 5, 8, 13, 21];
+        assert_eq!(
+            ContinuedFraction::pade_approximant(&fibonacci, 0, 2),
+            Some((vec![1], vec![1, -1, -1]))
+        );
+
+        // 1/(1 - x)^2 = 1 + 2x + 3x^2 + ..., [1/1] = (2 + x) / (2 - 3x)
+        assert_eq!(
+            ContinuedFraction::pade_approximant(&[1, 2, 3, 4, 5], 1, 1),
+            Some((vec![2, 1], vec![2, -3]))
+        );
+
+        // [L/0] is the truncated series
+        assert_eq!(
+            ContinuedFraction::pade_approximant(&[3, -1, 4, 1, 5], 2, 0),
+            Some((vec![3, -1, 4], vec![1]))
+        );
+
+        // x has no [0/1] approximant, Q(0) would have to vanish
+        assert_eq!(ContinuedFraction::pade_approximant(&[0, 1], 0, 1), None);
+
+        // Q*S - P vanishes up to x^(L+M) for an arbitrary series
+        let series = [2, -3, 1, 4, -1, 5, 9, -2, 6];
+        for l in 0..4 {
+            for m in 0..4 {
+                let Some((p, q)) = ContinuedFraction::pade_approximant(&series, l, m) else {
+                    continue;
+                };
+
+                assert!(p.len() <= l + 1 && q.len() <= m + 1, "[{}/{}]", l, m);
+                assert!(q[0] > 0);
+
+                for i in 0..=l + m {
+                    let qs: i64 = (0..=i)
+                        .map(|j| q.get(j).copied().unwrap_or(0) as i64 * series[i - j] as i64)
+                        .sum();
+                    let p_i = p.get(i).copied().unwrap_or(0) as i64;
+                    assert_eq!(qs, p_i, "[{}/{}] coefficient {}", l, m, i);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_three_distance_theorem() {
+        // 55/89 approximates 1/phi, with n = 89 the points are every multiple of 1/89
+        let golden = ContinuedFraction::from_rational(55, 89);
+        assert_eq!(golden.three_distance_theorem(89), vec![1.0 / 89.0]);
+        assert_eq!(golden.three_distance_theorem(200), vec![1.0 / 89.0]);
+
+        // For n a smaller Fibonacci number only two lengths remain, 21/89 and 34/89
+        assert_eq!(
+            golden.three_distance_theorem(3),
+            vec![21.0 / 89.0, 34.0 / 89.0]
+        );
+        assert_eq!(golden.three_distance_theorem(55).len(), 2);
+
+        // A single point leaves the whole circle as one gap
+        assert_eq!(golden.three_distance_theorem(1), vec![1.0]);
+        assert!(golden.three_distance_theorem(0).is_empty());
+
+        // Past q the points repeat, so even n = u32::MAX only places q of them
+        assert_eq!(golden.three_distance_theorem(u32::MAX), vec![1.0 / 89.0]);
+        let large = ContinuedFraction::from_rational(i32::MAX, 1000);
+        assert_eq!(large.three_distance_theorem(u32::MAX), vec![1.0 / 1000.0]);
+        assert_eq!(
+            large.three_distance_theorem(u32::MAX),
+            large.three_distance_theorem(1000)
+        );
+
+        for (p, q) in [(355, 113), (-3, 7), (1_346_269, 832_040), (1, 1000)] {
+            let cf = ContinuedFraction::from_rational(p, q);
+
+            for n in 1..150 {
+                let gaps = cf.three_distance_theorem(n);
+                assert!((1..=3).contains(&gaps.len()), "{}/{} with n = {}", p, q, n);
+                assert!(gaps.windows(2).all(|w| w[0] < w[1]));
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_kronecker_n() {
+        let circle = |x: f64, y: f64| {
+            let d = (x - y).rem_euclid(1.0);
+            d.min(1.0 - d)
+        };
+        let brute = |cf: &ContinuedFraction, beta: f64, epsilon: f64| {
+            let (p, q) = cf.to_rational_i64();
+            (1..=q)
+                .find(|&n| circle((n * p).rem_euclid(q) as f64 / q as f64, beta) < epsilon)
+                .map_or(0, |n| n as u64)
+        };
+
+        for (p, q) in [
+            (3, 7),
+            (22, 7),
+            (-3, 7),
+            (355, 113),
+            (89, 55),
+            (1, 1000),
+            (5, 1),
+        ] {
+            let cf = ContinuedFraction::from_rational(p, q);
+
+            for beta in [0.0, 0.1234567, 0.25, 0.5, 0.6180339, -0.3141592, 2.9] {
+                // Off the grid of multiples of 1/q, so float rounding cannot decide a tie
+                for epsilon in [0.5, 0.100_37, 0.030_761, 0.004_132_7, 0.000_131] {
+                    assert_eq!(
+                        cf.kronecker_n(beta, epsilon),
+                        brute(&cf, beta, epsilon),
+                        "{}/{}, beta = {}, epsilon = {}",
+                        p,
+                        q,
+                        beta,
+                        epsilon
+                    );
+                }
+            }
+        }
+
+        // phi has bounded coefficients, so the first n is of order 1/epsilon
+        let phi = ContinuedFraction::from_rational(1_346_269, 832_040);
+        for epsilon in [0.1, 0.01, 0.001, 0.0001] {
+            for beta in [0.123, 0.5, 0.987] {
+                let n = phi.kronecker_n(beta, epsilon);
+                assert!(
+                    n > 0 && (n as f64) < 2.0 / epsilon,
+                    "n = {} for {}",
+                    n,
+                    epsilon
+                );
+                assert!(circle(n as f64 * phi.to_f64(), beta) < epsilon);
+            }
+        }
+
+        // 1/1000 only reaches multiples of 0.001
+        let cf = ContinuedFraction::from_rational(1, 1000);
+        assert_eq!(cf.kronecker_n(0.0105, 0.0001), 0);
+
+        // q = 10^10 + 1 > u32::MAX, only the residue (q + 1) / 2 is in the window and
+        // reaching it takes more than u32::MAX steps
+        let cf = ContinuedFraction::from_coefficients(&[0, 1_000_000, 10_000]).unwrap();
+        let (p, q) = cf.to_rational_i64();
+        assert_eq!(q, 10_000_000_001);
+        let n = cf.kronecker_n(0.5 + 0.4 / q as f64, 0.5 / q as f64);
+        assert_eq!(n, 9_999_500_001);
+        assert_eq!(
+            (i128::from(n) * i128::from(p)) % i128::from(q),
+            i128::from(q / 2 + 1)
+        );
+
+        for (beta, epsilon) in [(f64::NAN, 0.1), (0.5, f64::NAN), (f64::INFINITY, 0.1)] {
+            assert!(std::panic::catch_unwind(|| cf.kronecker_n(beta, epsilon)).is_err());
+        }
+    }
+
+    #[test]
+    fn test_is_valid_cf_and_canonicalize() {
+        assert!(!is_valid_cf(&[]));
+
+        // Every sequence of length 1..=4 over -2..=3 agrees with `from_coefficients`, and
+        // canonicalizing a valid one keeps its value and matches `normalize`.
+        let mut sequences: Vec<Vec<i32>> = vec![vec![]];
+        for _ in 0..4 {
+            sequences = sequences
+                .iter()
+                .flat_map(|s| {
+                    (-2..=3).map(move |a| {
+                        let mut s = s.clone();
+                        s.push(a);
+                        s
+                    })
+                })
+                .collect();
+
+            for coefficients in &sequences {
+                let cf = ContinuedFraction::from_coefficients(coefficients);
+                assert_eq!(is_valid_cf(coefficients), cf.is_ok(), "{coefficients:?}");
+
+                if let Ok(cf) = cf {
+                    let mut canonical = coefficients.clone();
+                    canonicalize(&mut canonical);
+                    assert!(is_valid_cf(&canonical));
+                    assert_eq!(canonical, cf.normalize().coefficients());
+                    assert_eq!(
+                        ContinuedFraction::from_coefficients(&canonical)
+                            .unwrap()
+                            .to_rational(),
+                        cf.to_rational()
+                    );
+                }
+            }
+        }
+
+        let mut single_one = vec![1];
+        canonicalize(&mut single_one);
+        assert_eq!(single_one, vec![1]);
+
+        let mut empty = vec![];
+        canonicalize(&mut empty);
+        assert_eq!(empty, vec![0]);
+    }
+
+    #[test]
+    fn test_common_factor_is_not_reducible() {
+        assert_eq!(
+            ContinuedFraction::from_coefficients(&[2, 2])
+                .unwrap()
+                .to_rational(),
+            (5, 2)
+        );
+        assert_eq!(
+            ContinuedFraction::from_coefficients(&[1, 1])
+                .unwrap()
+                .to_rational(),
+            (2, 1)
+        );
+
+        for a0 in -6..=6 {
+            for a1 in 1..=6 {
+                for a2 in 1..=6 {
+                    let coefficients = [a0, a1, a2];
+                    let d = gcd_slice(&coefficients);
+                    if d < 2 {
+                        continue;
+                    }
+
+                    let reduced: Vec<i32> = coefficients.iter().map(|a| a / d).collect();
+                    assert_ne!(
+                        ContinuedFraction::from_coefficients(&reduced)
+                            .unwrap()
+                            .to_rational(),
+                        ContinuedFraction::from_coefficients(&coefficients)
+                            .unwrap()
+                            .to_rational(),
+                        "{coefficients:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_random() {
+        for seed in [0, 1, 42, u64::MAX] {
+            let cf = ContinuedFraction::generate_random(seed, 50);
+            assert_eq!(cf, ContinuedFraction::generate_random(seed, 50));
+            assert_eq!(cf.coefficients().len(), 50);
+            assert!(is_valid_cf(cf.coefficients()));
+            assert!((0..=100).contains(&cf.coefficients()[0]));
+            assert!(cf.coefficients()[1..].iter().all(|a| (1..=100).contains(a)));
+
+            // A shorter run is a prefix of a longer one
+            assert_eq!(
+                ContinuedFraction::generate_random(seed, 10).coefficients(),
+                &cf.coefficients()[..10]
+            );
+        }
+
+        assert_ne!(
+            ContinuedFraction::generate_random(1, 20),
+            ContinuedFraction::generate_random(2, 20)
+        );
+        assert_eq!(
+            ContinuedFraction::generate_random(7, 1)
+                .coefficients()
+                .len(),
+            1
+        );
     }
 }
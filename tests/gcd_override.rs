@@ -0,0 +1,47 @@
+//! Replacing the C `gcd` at link time, only built with the `gcd-override` feature.
+//!
+//! ```sh
+//! cargo test --features gcd-override --test gcd_override
+//! ```
+#![cfg(feature = "gcd-override")]
+
+use core::ffi::c_int;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use rust_c_riscv::{gcd, lcm};
+
+/// Same layout as `Pair` in `csrc/math/gcd.h`.
+#[repr(C)]
+struct Pair {
+    n: c_int,
+    m: c_int,
+}
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+/// Strong definition that takes the place of the weak one in `libgcd_impl.a`.
+#[unsafe(export_name = "gcd")]
+extern "C" fn counting_gcd(ps: &Pair) -> c_int {
+    CALLS.fetch_add(1, Ordering::SeqCst);
+
+    let (mut a, mut b) = (ps.n.unsigned_abs(), ps.m.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a as c_int
+}
+
+#[test]
+fn test_gcd_override() {
+    assert_eq!(gcd(48, 18), 6);
+    assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+    // Zero arguments are answered on the Rust side without calling the symbol
+    assert_eq!(gcd(0, -5), 5);
+    assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+    // The C `lcm` calls `gcd` through the symbol as well
+    assert_eq!(lcm(4, 6), 12);
+    assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+}
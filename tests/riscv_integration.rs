@@ -0,0 +1,49 @@
+//! End-to-end checks of the C code linked into a RISC-V binary.
+//!
+//! Only built for `target_arch = "riscv64"`, so it needs a cross toolchain and QEMU
+//! user-space emulation as the test runner. With `.cargo/config.toml` as checked in
+//! (`riscv64gc-unknown-linux-musl` with `runner = "qemu-riscv64"`) plain `cargo test`
+//! runs it; for the glibc target use
+//!
+//! ```sh
+//! CARGO_TARGET_RISCV64GC_UNKNOWN_LINUX_GNU_RUNNER=qemu-riscv64 \
+//!     cargo test --target riscv64gc-unknown-linux-gnu --test riscv_integration
+//! ```
+#![cfg(target_arch = "riscv64")]
+
+use rust_c_riscv::{ContinuedFraction, extended_gcd, gcd, gcd_u32, gcd64, lcm};
+
+#[test]
+fn test_c_bindings() {
+    assert_eq!(gcd(48, 18), 6);
+    assert_eq!(gcd(17, 13), 1);
+    assert_eq!(gcd(0, 5), 5);
+
+    assert_eq!(lcm(4, 6), 12);
+    assert_eq!(gcd_u32(u32::MAX, 3), 3);
+    assert_eq!(gcd64(12_586_269_025, 7_778_742_049), 1);
+
+    let (g, s, t) = extended_gcd(240, 46);
+    assert_eq!(g, 2);
+    assert_eq!(240 * s + 46 * t, g);
+}
+
+#[test]
+fn test_continued_fraction() {
+    let cf = ContinuedFraction::from_rational(415, 93);
+    assert_eq!(cf.coefficients(), &[4, 2, 6, 7]);
+    assert_eq!(cf.to_rational(), (415, 93));
+    assert_eq!(cf.convergents(), vec![(4, 1), (9, 2), (58, 13), (415, 93)]);
+
+    // Reduced through the C gcd
+    assert_eq!(
+        ContinuedFraction::from_rational(6, 14),
+        ContinuedFraction::from_rational(3, 7)
+    );
+
+    let pi = ContinuedFraction::from_coefficients(&[3, 7, 15, 1, 292]).unwrap();
+    assert_eq!(pi.best_approximation(1000), (355, 113));
+
+    let sum = ContinuedFraction::from_rational(1, 2) + ContinuedFraction::from_rational(1, 3);
+    assert_eq!(sum.to_rational(), (5, 6));
+}